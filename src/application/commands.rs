@@ -1,9 +1,34 @@
-use crate::cli::{Cli, Commands};
-use crate::core::changelog::{parse_header_format, ChangelogDocument, Release};
-use crate::core::git::{latest_semver_tag, CommitRange, Git2Repository, RepositoryApi};
-use crate::core::notes::notes_from_commits;
+use crate::cli::{Cli, Commands, TemplatesCommand};
+use crate::config::loader::{discover_config, load_config_file};
+use crate::config::settings::Settings;
+use crate::core::bump::{infer_bump, BumpLevel};
+use crate::core::changelog::{
+    parse_header_format, ChangelogDocument, CompareLinks, DocumentFormat, Release, ReleaseVersion,
+};
+use crate::core::commit_parser::CommitParsers;
+use crate::core::context::{DocumentContext, TemplateContext, TemplateRepository};
+use crate::core::feed;
+use crate::core::forge::{Forge, ForgeKind, ForgeRelease};
+use crate::core::git::{
+    latest_semver_tag, latest_tag_with_prefix, tag_version, CommitRange, Git2Repository,
+    RepositoryApi,
+};
+use crate::core::notes::{
+    collect_notes, group_notes, has_ignore_marker, map_labels_to_section, map_type_to_section,
+    parse_conventional_header, should_ignore_commit, Note, PullRequestLink, ScopeFilter,
+    ScopeStyle,
+};
+use crate::core::packaging::{parse_debian, PackageFormat, Packaging};
+use crate::core::processors::TextProcessors;
+use crate::domain::commit::Commit;
 use crate::error::{ChangelogError, Result};
+use crate::infrastructure::github::{repository_slug, GitHubClient, PullRequest, PullRequestCache};
+use crate::infrastructure::gitlab::GitLabClient;
+use crate::infrastructure::templates::{TemplateOrigin, TemplateRegistry};
+use crate::infrastructure::version_files::{bump_version_files, VersionFile};
+use crate::infrastructure::workspace::{detect_workspace, WorkspaceMember};
 use clap::Parser;
+use indexmap::IndexMap;
 use semver::Version;
 use serde::Deserialize;
 use std::collections::BTreeMap;
@@ -12,10 +37,12 @@ use std::path::{Path, PathBuf};
 
 pub async fn execute() -> Result<()> {
     let cli = Cli::parse();
+    let settings = load_settings(cli.config.as_deref())?;
+    let resolve_file = |file: Option<PathBuf>| file.unwrap_or_else(|| settings.file.clone());
 
     match cli.command {
-        Commands::New { file, format } => command_new(&file, &format),
-        Commands::Validate { file, strict } => command_validate(&file, strict),
+        Commands::New { file, format } => command_new(&resolve_file(file), format.as_deref()),
+        Commands::Validate { file, strict } => command_validate(&resolve_file(file), strict),
         Commands::Generate {
             file,
             since,
@@ -26,9 +53,16 @@ pub async fn execute() -> Result<()> {
             template,
             output,
             map,
+            scopes,
+            exclude_scopes,
+            scope_style,
+            paths,
+            exclude_paths,
+            workspace,
+            github_api_url,
         } => {
             let args = GenerateArgs {
-                file,
+                file: resolve_file(file),
                 since,
                 until,
                 specific,
@@ -37,60 +71,292 @@ pub async fn execute() -> Result<()> {
                 template,
                 output,
                 map,
+                scope_filter: ScopeFilter {
+                    include: scopes,
+                    exclude: exclude_scopes,
+                },
+                scope_style,
+                paths,
+                exclude_paths,
+                workspace,
+                github_api_url,
             };
-            command_generate(args).await
+            command_generate(args, &settings).await
         }
         Commands::Release {
             version,
             bump,
+            preid,
+            build,
+            finalize,
             file,
             header,
             override_existing,
+            tag_prefix,
+            no_links,
+            map,
+            scope_style,
+            workspace,
+            commit,
+            tag,
+            commit_message,
+            stage,
+            version_files,
+            allow_dirty,
+            template,
         } => {
+            let version_files = match version_files.is_empty() {
+                true => settings.version_files.clone(),
+                false => version_files
+                    .into_iter()
+                    .map(|path| VersionFile::new(path, None))
+                    .collect(),
+            };
             let args = ReleaseArgs {
                 version,
                 bump,
-                file,
-                header,
+                preid,
+                build,
+                finalize,
+                file: resolve_file(file),
+                header: header.unwrap_or_else(|| settings.header.clone()),
                 override_existing,
+                tag_prefix: tag_prefix.unwrap_or_else(|| settings.tag_prefix.clone()),
+                no_links,
+                map,
+                scope_style,
+                workspace,
+                commit,
+                tag,
+                commit_message: commit_message.unwrap_or_else(|| settings.commit_message.clone()),
+                stage,
+                version_files,
+                allow_dirty,
+                template: template.or_else(|| settings.template.clone()),
             };
-            command_release(args).await
+            command_release(args, &settings).await
         }
         Commands::Show {
             file,
             version,
             range,
             converge,
-        } => command_show(&file, version.as_deref(), range.as_deref(), converge),
-        Commands::Remove { version, file, yes } => command_remove(&file, &version, yes),
+            format,
+            site_url,
+            template,
+        } => command_show(
+            &resolve_file(file),
+            version.as_deref(),
+            range.as_deref(),
+            converge,
+            format.as_deref(),
+            site_url.as_deref(),
+            template.or_else(|| settings.template.clone()).as_deref(),
+        ),
+        Commands::Export {
+            format,
+            file,
+            output,
+            package,
+            maintainer,
+        } => {
+            let mut packaging = settings.packaging.clone();
+            packaging.package = package.or(packaging.package);
+            packaging.maintainer = maintainer.or(packaging.maintainer);
+            command_export(&format, &resolve_file(file), output.as_deref(), &packaging)
+        }
+        Commands::Import {
+            input,
+            format,
+            file,
+            header,
+            strict,
+        } => command_import(
+            &input,
+            format.as_deref(),
+            &resolve_file(file),
+            &header.unwrap_or_else(|| settings.header.clone()),
+            strict,
+        ),
+        Commands::Remove { version, file, yes } => command_remove(
+            &resolve_file(file),
+            &version,
+            yes,
+            settings.template.as_deref(),
+        ),
+        Commands::Publish {
+            version,
+            file,
+            tag_prefix,
+            forge,
+            draft,
+            prerelease,
+            dry_run,
+            github_api_url,
+            gitlab_api_url,
+        } => {
+            let args = PublishArgs {
+                version,
+                file: resolve_file(file),
+                tag_prefix: tag_prefix.unwrap_or_else(|| settings.tag_prefix.clone()),
+                forge,
+                draft,
+                prerelease,
+                dry_run,
+                github_api_url: github_api_url.unwrap_or_else(|| settings.github_api_url.clone()),
+                gitlab_api_url,
+            };
+            command_publish(args).await
+        }
+        Commands::Templates { command } => match command {
+            TemplatesCommand::List => command_templates_list(),
+            TemplatesCommand::Show { name } => command_templates_show(&name),
+        },
+        Commands::TestParser { messages, map } => {
+            let mapping = load_mapping(&settings, map.as_deref())?;
+            command_test_parser(&messages, &mapping, &settings);
+            Ok(())
+        }
     }
 }
 
-fn command_new(file: &Path, format: &str) -> Result<()> {
-    if format != "markdown" {
-        return Err(ChangelogError::InvalidArguments(format!(
-            "unsupported format '{}'; only 'markdown' is currently supported",
-            format
-        )));
-    }
+/// Loads `--config` (or `$CHANGELOGGEN_CONFIG`), else the `changelog.toml`
+/// discovered from the current directory, else the built-in defaults.
+fn load_settings(explicit: Option<&Path>) -> Result<Settings> {
+    let path = match explicit {
+        Some(path) if !path.is_file() => {
+            return Err(ChangelogError::ConfigNotFound(path.to_path_buf()));
+        }
+        Some(path) => Some(path.to_path_buf()),
+        // Relative to the current directory where possible, so paths
+        // resolved against the config stay short in messages.
+        None => {
+            let cwd = std::env::current_dir()?;
+            discover_config(&cwd).map(|path| {
+                path.strip_prefix(&cwd)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(path)
+            })
+        }
+    };
+
+    let Some(path) = path else {
+        return Ok(Settings::default());
+    };
+
+    let config = load_config_file(&path).map_err(|e| {
+        let format = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("config");
+        ChangelogError::config_parse_error(format, &format!("{}: {}", path.display(), e))
+    })?;
+    Ok(Settings::from_config(config, Some(path)))
+}
 
-    let doc = ChangelogDocument::scaffold();
-    write_file(file, &doc.to_markdown())?;
+fn command_new(file: &Path, format: Option<&str>) -> Result<()> {
+    let format = match format {
+        Some(format) => parse_document_format(format)?,
+        None => document_format(file),
+    };
+    if !format.is_readable() {
+        return Err(ChangelogError::InvalidArguments(
+            "new can only create markdown, asciidoc, rst, json, yaml or toml changelogs"
+                .to_string(),
+        ));
+    }
+    write_document(file, &ChangelogDocument::scaffold(), format, None)?;
     println!("Created {}", file.display());
     Ok(())
 }
 
 fn command_validate(file: &Path, strict: bool) -> Result<()> {
-    let content = read_or_create_scaffold(file)?;
-    let document = ChangelogDocument::parse(&content).map_err(|issue| {
+    let document = match file.exists() {
+        true => ChangelogDocument::load(&fs::read_to_string(file)?, document_format(file))
+            .map_err(|e| ChangelogError::InvalidArguments(format!("validation failed: {}", e)))?,
+        false => ChangelogDocument::scaffold(),
+    };
+
+    document.validate(strict).map_err(|issue| {
         ChangelogError::InvalidArguments(format!("validation failed: {}", issue.message()))
     })?;
 
+    println!("{} is valid", file.display());
+    Ok(())
+}
+
+fn command_import(
+    input: &Path,
+    format: Option<&str>,
+    file: &Path,
+    header: &str,
+    strict: bool,
+) -> Result<()> {
+    // `None` stands for a debian/changelog.
+    let format = match format {
+        Some("debian") => None,
+        Some(format) => Some(parse_document_format(format)?),
+        None if is_debian_changelog(input) => None,
+        None => Some(DocumentFormat::from_path(input).ok_or_else(|| {
+            ChangelogError::InvalidArguments(format!(
+                "cannot tell the format of {}; pass --format",
+                input.display()
+            ))
+        })?),
+    };
+
+    let content = fs::read_to_string(input)?;
+    let loaded = match format {
+        Some(format) => ChangelogDocument::load(&content, format),
+        None => parse_debian(&content),
+    };
+    let mut document = loaded.map_err(|e| {
+        ChangelogError::InvalidArguments(format!("cannot import {}: {}", input.display(), e))
+    })?;
     document.validate(strict).map_err(|issue| {
         ChangelogError::InvalidArguments(format!("validation failed: {}", issue.message()))
     })?;
 
-    println!("{} is valid", file.display());
+    let header = parse_header_format(header);
+    for release in &mut document.releases {
+        release.header = header.clone();
+    }
+    write_document(file, &document, document_format(file), None)?;
+    println!("Wrote {} from {}", file.display(), input.display());
+    Ok(())
+}
+
+fn is_debian_changelog(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "changelog")
+        && path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .is_some_and(|dir| dir == "debian")
+}
+
+fn command_export(
+    format: &str,
+    file: &Path,
+    output: Option<&Path>,
+    packaging: &Packaging,
+) -> Result<()> {
+    let format = PackageFormat::parse(format).ok_or_else(|| {
+        ChangelogError::InvalidArguments(
+            "--format must be one of: debian, rpm, appstream".to_string(),
+        )
+    })?;
+    let document = load_or_scaffold(file)?;
+    let rendered = format
+        .render(&document, packaging)
+        .map_err(|e| ChangelogError::InvalidArguments(format!("cannot export: {}", e)))?;
+
+    match output {
+        Some(output) => {
+            write_file(output, &rendered)?;
+            println!("Wrote {}", output.display());
+        }
+        None => print!("{}", rendered),
+    }
     Ok(())
 }
 
@@ -105,102 +371,656 @@ struct GenerateArgs {
     template: Option<PathBuf>,
     output: Option<PathBuf>,
     map: Option<PathBuf>,
+    scope_filter: ScopeFilter,
+    scope_style: String,
+    paths: Vec<String>,
+    exclude_paths: Vec<String>,
+    workspace: bool,
+    github_api_url: Option<String>,
 }
 
-async fn command_generate(args: GenerateArgs) -> Result<()> {
+async fn command_generate(args: GenerateArgs, settings: &Settings) -> Result<()> {
     if args.milestone.is_some() && !args.github {
         return Err(ChangelogError::InvalidArguments(
             "--milestone requires --github".to_string(),
         ));
     }
 
-    if args.milestone.is_some() {
-        return Err(ChangelogError::UnsupportedFeature(
-            "milestone mode requires GitHub API integration, which is not yet enabled".to_string(),
-        ));
-    }
-
     let _existing_doc = load_or_scaffold(&args.file)?;
 
     let repo = Git2Repository::open(".").map_err(|e| ChangelogError::GitError(e.to_string()))?;
+    // Pathspecs are given relative to the current directory; git reports
+    // paths relative to the repository root.
+    let base = repo_base(&repo)?;
     let range = CommitRange {
-        since: args.since,
-        until: args.until,
-        specific: args.specific,
+        since: args.since.clone(),
+        until: args.until.clone(),
+        specific: args.specific.clone(),
+        include_paths: repo_pathspecs(&base, &args.paths),
+        exclude_paths: repo_pathspecs(&base, &args.exclude_paths),
     };
-    let commits = repo
-        .list_commits(&range)
-        .map_err(|e| ChangelogError::GitError(e.to_string()))?;
 
-    let mapping = load_type_mapping(args.map.as_deref())?;
-    let grouped = notes_from_commits(&commits, &mapping);
+    let mapping = load_mapping(settings, args.map.as_deref())?;
+    let scope_style = parse_scope_style(&args.scope_style)?;
+    let forge = repo
+        .remote_url("origin")
+        .map_err(|e| ChangelogError::GitError(e.to_string()))?
+        .and_then(|url| Forge::from_remote(&url, None));
+    let template = match &args.template {
+        Some(template) => {
+            let mut registry =
+                TemplateRegistry::load().map_err(|e| ChangelogError::template(e.to_string()))?;
+            let name = registry
+                .resolve(template)
+                .map_err(|e| ChangelogError::template(e.to_string()))?;
+            Some((registry, name))
+        }
+        None => None,
+    };
+    let render_notes = |notes: Vec<Note>, commits: &[Commit]| -> Result<String> {
+        let notes: Vec<Note> = mapping
+            .postprocess(notes)
+            .into_iter()
+            .filter(|note| args.scope_filter.allows(note.scope.as_deref()))
+            .collect();
+        let Some((registry, name)) = &template else {
+            return Ok(render_generated_sections(group_notes(&notes, scope_style)));
+        };
+
+        let mut context = TemplateContext::new(&notes, commits);
+        describe_range(&mut context, &args, settings, forge.as_ref());
+        let rendered = registry
+            .render(name, &context)
+            .map_err(|e| ChangelogError::template(e.to_string()))?;
+        Ok(rendered.trim_end().to_string() + "\n")
+    };
+    let render = |commits: &[Commit], prs: &BTreeMap<u64, PullRequest>| -> Result<String> {
+        let commits = without_ignored(commits.to_vec(), settings);
+        render_notes(enriched_notes(&commits, &mapping, settings, prs), &commits)
+    };
 
-    let markdown = render_generated_sections(grouped, args.template.as_deref())
-        .map_err(|e| ChangelogError::TemplateError(e.to_string()))?;
+    let github = match args.github {
+        true => Some(GitHubRepository::from_origin(&repo, &args, settings)?),
+        false => None,
+    };
 
-    if let Some(output) = args.output {
-        write_file(&output, &markdown)?;
-        println!("Wrote generated notes to {}", output.display());
-    } else {
-        println!("{}", markdown);
+    if !args.workspace {
+        let markdown = match (&args.milestone, &github) {
+            (Some(milestone), Some(github)) => render_notes(
+                milestone_notes(github, settings, &mapping, milestone).await?,
+                &[],
+            )?,
+            _ => {
+                let commits = mapping.preprocess(
+                    repo.list_commits(&range)
+                        .map_err(|e| ChangelogError::GitError(e.to_string()))?,
+                );
+                let prs = match &github {
+                    Some(github) => github.pull_requests(&commits).await,
+                    None => BTreeMap::new(),
+                };
+                render(&commits, &prs)?
+            }
+        };
+        if let Some(output) = &args.output {
+            write_file(output, &markdown)?;
+            println!("Wrote generated notes to {}", output.display());
+        } else {
+            println!("{}", markdown);
+        }
+        return Ok(());
+    }
+
+    // One block of notes per crate, written next to each crate's manifest
+    // when --output is given.
+    for located in workspace_members(&repo)? {
+        let member_range = located.narrow(&range);
+        let member_commits = mapping.preprocess(
+            repo.list_commits(&member_range)
+                .map_err(|e| ChangelogError::GitError(e.to_string()))?,
+        );
+        if member_commits.is_empty() {
+            continue;
+        }
+
+        let prs = match &github {
+            Some(github) => github.pull_requests(&member_commits).await,
+            None => BTreeMap::new(),
+        };
+        let markdown = render(&member_commits, &prs)?;
+        if let Some(output) = &args.output {
+            let path = located.member.path.join(output);
+            write_file(&path, &markdown)?;
+            println!("Wrote generated notes to {}", path.display());
+        } else {
+            println!("## {}\n\n{}", located.member.name, markdown);
+        }
     }
 
     Ok(())
 }
 
+/// Notes for the closed issues and merged pull requests of a GitHub
+/// milestone. Labels pick the section; otherwise a conventional title is
+/// mapped like a commit.
+async fn milestone_notes(
+    github: &GitHubRepository,
+    settings: &Settings,
+    mapping: &TypeMapping,
+    milestone: &str,
+) -> Result<Vec<Note>> {
+    let milestone = github
+        .client
+        .find_milestone(&github.slug, milestone)
+        .await
+        .map_err(|e| ChangelogError::GitHubApiError(e.to_string()))?;
+    let items = github
+        .client
+        .milestone_items(&github.slug, milestone.number)
+        .await
+        .map_err(|e| ChangelogError::GitHubApiError(e.to_string()))?;
+
+    let mut notes = Vec::new();
+    for item in items {
+        if has_ignore_marker(&item.title, &settings.ignore_markers)
+            || should_ignore_commit(&item.title)
+        {
+            continue;
+        }
+
+        let header = parse_conventional_header(&item.title);
+        let section = map_labels_to_section(&item.label_names(), &settings.labels)
+            .or_else(|| {
+                header
+                    .as_ref()
+                    .map(|h| map_type_to_section(&h.kind, &mapping.types))
+            })
+            .unwrap_or_else(|| "Other".to_string());
+        let (text, scope) = match header {
+            Some(header) => (header.description, header.scope),
+            None => (item.title.clone(), None),
+        };
+
+        // Pull requests are credited like linked commits; plain issues keep
+        // the link in their text.
+        let (text, pull_request) = match item.pull_request {
+            Some(_) => (
+                text,
+                Some(PullRequestLink {
+                    number: item.number,
+                    url: item.html_url.clone(),
+                    author: None,
+                }),
+            ),
+            None => (
+                format!("{} ([#{}]({}))", text, item.number, item.html_url),
+                None,
+            ),
+        };
+        notes.push(Note {
+            section,
+            text,
+            scope,
+            pull_request,
+            ..Note::default()
+        });
+    }
+    Ok(notes)
+}
+
+/// The GitHub repository behind the `origin` remote, for `generate --github`.
+struct GitHubRepository {
+    client: GitHubClient,
+    slug: String,
+    cache: Option<PullRequestCache>,
+}
+
+impl GitHubRepository {
+    fn from_origin(
+        repo: &Git2Repository,
+        args: &GenerateArgs,
+        settings: &Settings,
+    ) -> Result<Self> {
+        let remote = repo
+            .remote_url("origin")
+            .map_err(|e| ChangelogError::GitError(e.to_string()))?
+            .ok_or_else(|| {
+                ChangelogError::InvalidArguments(
+                    "--github needs an `origin` remote pointing at GitHub".to_string(),
+                )
+            })?;
+        let slug = repository_slug(&remote).ok_or_else(|| {
+            ChangelogError::InvalidArguments(format!(
+                "cannot derive a GitHub repository from '{}'",
+                remote
+            ))
+        })?;
+
+        let api_url = args
+            .github_api_url
+            .clone()
+            .unwrap_or_else(|| settings.github_api_url.clone());
+        Ok(Self {
+            client: GitHubClient::new(api_url, std::env::var("GITHUB_TOKEN").ok()),
+            slug,
+            cache: PullRequestCache::default_dir().map(PullRequestCache::new),
+        })
+    }
+
+    /// Pull requests referenced by `commits`, from the cache when possible.
+    /// A reference that cannot be fetched is reported and left out, as is
+    /// a cache write that fails.
+    async fn pull_requests(&self, commits: &[Commit]) -> BTreeMap<u64, PullRequest> {
+        let mut prs = BTreeMap::new();
+        for number in commits.iter().filter_map(Commit::pull_request_number) {
+            if prs.contains_key(&number) {
+                continue;
+            }
+            if let Some(pr) = self.cache.as_ref().and_then(|c| c.get(&self.slug, number)) {
+                prs.insert(number, pr);
+                continue;
+            }
+
+            match self.client.fetch_pr(&self.slug, number).await {
+                Ok(pr) => {
+                    // The cache only saves API calls; a failed write is not fatal.
+                    if let Some(Err(e)) = self.cache.as_ref().map(|c| c.put(&self.slug, &pr)) {
+                        eprintln!("Warning: cannot cache pull request #{}: {}", number, e);
+                    }
+                    prs.insert(number, pr);
+                }
+                Err(e) => eprintln!("Warning: skipping pull request #{}: {}", number, e),
+            }
+        }
+        prs
+    }
+}
+
+/// Notes for `commits`, with referenced pull requests linked and credited.
+/// Merge commits use the pull request title; unconventional messages take
+/// their section from the pull request labels.
+fn enriched_notes(
+    commits: &[Commit],
+    mapping: &TypeMapping,
+    settings: &Settings,
+    prs: &BTreeMap<u64, PullRequest>,
+) -> Vec<Note> {
+    let mut notes = Vec::new();
+    for commit in commits {
+        let Some(pr) = commit.pull_request_number().and_then(|n| prs.get(&n)) else {
+            notes.extend(collect_notes(
+                std::slice::from_ref(commit),
+                &mapping.types,
+                &mapping.parsers,
+            ));
+            continue;
+        };
+
+        let mut commit = commit.clone();
+        commit.message = if commit.message.starts_with("Merge pull request #") {
+            pr.title.clone()
+        } else {
+            commit.summary_without_pr_reference().to_string()
+        };
+
+        let link = PullRequestLink {
+            number: pr.number,
+            url: pr.html_url.clone(),
+            author: pr.user.as_ref().map(|user| user.login.clone()),
+        };
+        let label_section = (parse_conventional_header(&commit.message).is_none()
+            && mapping.parsers.parse(&commit.message).is_none())
+        .then(|| map_labels_to_section(&pr.label_names(), &settings.labels))
+        .flatten();
+
+        for mut note in collect_notes(
+            std::slice::from_ref(&commit),
+            &mapping.types,
+            &mapping.parsers,
+        ) {
+            if let Some(section) = &label_section {
+                note.section = section.clone();
+            }
+            note.pull_request = Some(link.clone());
+            notes.push(note);
+        }
+    }
+    notes
+}
+
 #[derive(Debug)]
 struct ReleaseArgs {
     version: Option<String>,
     bump: Option<String>,
+    preid: String,
+    build: Option<String>,
+    finalize: bool,
     file: PathBuf,
     header: String,
     override_existing: bool,
+    tag_prefix: String,
+    no_links: bool,
+    map: Option<PathBuf>,
+    scope_style: String,
+    workspace: bool,
+    commit: bool,
+    tag: bool,
+    commit_message: String,
+    stage: Vec<PathBuf>,
+    /// Bumped along with the changelog; not used with `--workspace`.
+    version_files: Vec<VersionFile>,
+    allow_dirty: bool,
+    template: Option<PathBuf>,
 }
 
-async fn command_release(args: ReleaseArgs) -> Result<()> {
-    let mut document = load_or_scaffold(&args.file)?;
+/// A changelog maintained by `release`: the repository itself or one crate
+/// of a Cargo workspace.
+#[derive(Debug)]
+struct ReleaseTarget {
+    crate_name: Option<String>,
+    file: PathBuf,
+    tag_prefix: String,
+    /// Crate whose commits belong to this changelog; `None` for the
+    /// repository itself.
+    member: Option<LocatedMember>,
+}
 
-    let new_version = match (args.version, args.bump.as_deref()) {
-        (Some(raw), None) => {
-            Version::parse(&raw).map_err(|_| ChangelogError::VersionParseError(raw.to_string()))?
-        }
-        (None, Some(bump)) => {
-            let base = document
-                .releases
-                .iter()
-                .map(|r| r.version.clone())
-                .max()
-                .unwrap_or_else(|| Version::new(0, 0, 0));
-            bump_version(base, bump)?
-        }
-        _ => {
+/// A release written by `release_target`, for `--commit` and `--tag`.
+#[derive(Debug)]
+struct ReleasedEntry {
+    crate_name: Option<String>,
+    version: Version,
+    tag: String,
+    /// The changelog and any bumped version files.
+    files: Vec<PathBuf>,
+    notes: String,
+}
+
+async fn command_release(args: ReleaseArgs, settings: &Settings) -> Result<()> {
+    let repo = Git2Repository::open(".").map_err(|e| ChangelogError::GitError(e.to_string()))?;
+
+    if args.commit && !args.allow_dirty {
+        let dirty = repo
+            .is_dirty()
+            .map_err(|e| ChangelogError::GitError(e.to_string()))?;
+        if dirty {
             return Err(ChangelogError::InvalidArguments(
-                "use exactly one of --version or --bump".to_string(),
+                "the working tree has uncommitted changes; commit them or pass --allow-dirty"
+                    .to_string(),
             ));
         }
+    }
+
+    let mut released = Vec::new();
+    if !args.workspace {
+        let target = ReleaseTarget {
+            crate_name: None,
+            file: args.file.clone(),
+            tag_prefix: args.tag_prefix.clone(),
+            member: None,
+        };
+        released.extend(release_target(&repo, &args, settings, &target)?);
+    } else {
+        if !args.version_files.is_empty() {
+            println!(
+                "Skipping the configured version files with --workspace; \
+                 each crate's Cargo.toml is bumped instead"
+            );
+        }
+        // A changelog path from the config names the file inside each
+        // crate, not the one next to the config.
+        let file = args
+            .file
+            .strip_prefix(settings.config_dir())
+            .unwrap_or(&args.file);
+        let targets: Vec<ReleaseTarget> = workspace_members(&repo)?
+            .into_iter()
+            .map(|located| ReleaseTarget {
+                crate_name: Some(located.member.name.clone()),
+                file: located.member.path.join(file),
+                tag_prefix: located.member.tag_prefix(&args.tag_prefix),
+                member: Some(located),
+            })
+            .collect();
+        // Check every crate before the first changelog is written.
+        for target in &targets {
+            target_version_files(&args, target)?;
+        }
+        for target in &targets {
+            released.extend(release_target(&repo, &args, settings, target)?);
+        }
+    }
+
+    if args.commit && !released.is_empty() {
+        commit_release(&repo, &args, &released)?;
+    }
+    Ok(())
+}
+
+/// Commits the updated changelogs plus `--stage` files and, with `--tag`,
+/// creates one annotated tag per release carrying its notes.
+fn commit_release(
+    repo: &Git2Repository,
+    args: &ReleaseArgs,
+    released: &[ReleasedEntry],
+) -> Result<()> {
+    let base = repo_base(repo)?;
+    let root = repo.workdir().map(Path::canonicalize).transpose()?;
+    let to_repo_path = |path: &Path| match &root {
+        Some(root) if path.is_absolute() => {
+            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            path.strip_prefix(root).unwrap_or(&path).to_path_buf()
+        }
+        _ => base.join(path),
     };
+    let mut paths: Vec<PathBuf> = released
+        .iter()
+        .flat_map(|entry| &entry.files)
+        .map(|path| to_repo_path(path))
+        .collect();
+    paths.extend(args.stage.iter().map(|path| to_repo_path(path)));
+
+    let version = released
+        .iter()
+        .map(|entry| match &entry.crate_name {
+            Some(name) => format!("{} {}", name, entry.version),
+            None => entry.version.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let tags = released
+        .iter()
+        .map(|entry| entry.tag.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = args
+        .commit_message
+        .replace("{version}", &version)
+        .replace("{tag}", &tags);
+
+    let hash = repo
+        .commit_files(&paths, &message)
+        .map_err(|e| ChangelogError::GitError(e.to_string()))?;
+    println!("Committed {} {}", &hash[..7], message);
+
+    if args.tag {
+        for entry in released {
+            let notes = match entry.notes.is_empty() {
+                true => entry.tag.clone(),
+                false => entry.notes.clone(),
+            };
+            repo.create_annotated_tag(&entry.tag, &notes)
+                .map_err(|e| ChangelogError::GitError(e.to_string()))?;
+            println!("Tagged {}", entry.tag);
+        }
+    }
+    Ok(())
+}
+
+fn release_target(
+    repo: &Git2Repository,
+    args: &ReleaseArgs,
+    settings: &Settings,
+    target: &ReleaseTarget,
+) -> Result<Option<ReleasedEntry>> {
+    let mut document = load_or_scaffold(&target.file)?;
 
-    let repo = Git2Repository::open(".").map_err(|e| ChangelogError::GitError(e.to_string()))?;
     let tags = repo
         .list_tags()
         .map_err(|e| ChangelogError::GitError(e.to_string()))?;
 
-    let range = CommitRange {
-        since: latest_semver_tag(&tags),
-        until: None,
-        specific: None,
+    // Crate tags must carry the crate prefix; the repository itself also
+    // accepts any SemVer tag for compatibility with older tag schemes.
+    let since = match target.crate_name {
+        Some(_) => latest_tag_with_prefix(&tags, &target.tag_prefix),
+        None => {
+            latest_tag_with_prefix(&tags, &target.tag_prefix).or_else(|| latest_semver_tag(&tags))
+        }
+    };
+    let tagged = since
+        .as_deref()
+        .and_then(|tag| tag_version(tag, &target.tag_prefix));
+    let mut range = CommitRange {
+        since,
+        ..CommitRange::default()
     };
+    if let Some(located) = &target.member {
+        range = located.narrow(&range);
+    }
 
+    let mapping = load_mapping(settings, args.map.as_deref())?;
     let commits = repo
         .list_commits(&range)
         .map_err(|e| ChangelogError::GitError(e.to_string()))?;
+    let commits = without_ignored(mapping.preprocess(commits), settings);
+
+    if target.member.is_some() && commits.is_empty() {
+        println!(
+            "No changes for {}; skipping",
+            target.crate_name.as_deref().unwrap_or_default()
+        );
+        return Ok(None);
+    }
+
+    let mut new_version = match (args.version.as_deref(), args.bump.as_deref()) {
+        (Some(raw), None) => {
+            Version::parse(raw).map_err(|_| ChangelogError::VersionParseError(raw.to_string()))?
+        }
+        (None, Some(bump)) => {
+            // Commits are counted since the latest tag, so bump from it when
+            // the changelog lags behind the tags.
+            let base = match document.latest_version().cloned().max(tagged) {
+                Some(version) => version,
+                None => {
+                    version_file_version(args, target)?.unwrap_or_else(|| Version::new(0, 0, 0))
+                }
+            };
+            if bump == "auto" {
+                let decision =
+                    infer_bump(&commits, &mapping.bump_rules()?, &mapping.parsers, &base);
+                if decision.level == BumpLevel::None {
+                    return Err(ChangelogError::InvalidArguments(format!(
+                        "--bump auto found nothing to release: {}",
+                        decision.reason
+                    )));
+                }
+                match &target.crate_name {
+                    Some(name) => println!("Selected {} for {}", decision.reason, name),
+                    None => println!("Selected {}", decision.reason),
+                }
+                decision.level.apply(base)
+            } else if let Some(level) = bump.strip_prefix("pre") {
+                let level = match level {
+                    "release" => None,
+                    other => Some(parse_bump_level(other)?),
+                };
+                BumpLevel::apply_prerelease(level, base, &args.preid).map_err(|e| {
+                    ChangelogError::InvalidArguments(format!(
+                        "invalid --preid '{}': {}",
+                        args.preid, e
+                    ))
+                })?
+            } else {
+                parse_bump_level(bump)?.apply(base)
+            }
+        }
+        (None, None) if args.finalize => {
+            let latest = document
+                .latest_version()
+                .filter(|version| !version.pre.is_empty())
+                .cloned()
+                .ok_or_else(|| {
+                    ChangelogError::InvalidArguments(
+                        "--finalize without --version or --bump needs a pre-release as the latest release"
+                            .to_string(),
+                    )
+                })?;
+            BumpLevel::Patch.apply(latest)
+        }
+        _ => {
+            return Err(ChangelogError::InvalidArguments(
+                "use exactly one of --version or --bump".to_string(),
+            ));
+        }
+    };
 
-    let mapping = load_type_mapping(None)?;
-    let grouped = notes_from_commits(&commits, &mapping);
+    if let Some(build) = &args.build {
+        new_version.build = semver::BuildMetadata::new(build).map_err(|_| {
+            ChangelogError::InvalidArguments(format!("invalid --build metadata '{}'", build))
+        })?;
+    }
+
+    if args.finalize && !new_version.pre.is_empty() {
+        return Err(ChangelogError::InvalidArguments(format!(
+            "--finalize needs a final version, got {}",
+            new_version
+        )));
+    }
 
-    let mut release = Release::new(new_version);
+    let tag = format!("{}{}", target.tag_prefix, new_version);
+    if args.tag && tags.contains(&tag) {
+        return Err(ChangelogError::InvalidArguments(format!(
+            "tag {} already exists",
+            tag
+        )));
+    }
+
+    let grouped = group_notes(
+        &mapping.postprocess(collect_notes(&commits, &mapping.types, &mapping.parsers)),
+        parse_scope_style(&args.scope_style)?,
+    );
+
+    let mut release = Release::new(new_version.clone());
     release.header = parse_header_format(&args.header);
-    release.sections = grouped;
+
+    // Hand-written notes from `## [Unreleased]` come first and the generated
+    // ones last; an empty Unreleased heading stays for the next cycle.
+    if let Some(unreleased) = document.take_unreleased() {
+        document.releases.insert(
+            0,
+            Release {
+                description: String::new(),
+                sections: IndexMap::new(),
+                section_descriptions: BTreeMap::new(),
+                ..unreleased.clone()
+            },
+        );
+        release.absorb(unreleased);
+    }
+
+    // Pre-release entries are folded in next, oldest first, so the final
+    // release lists everything shipped since the previous final version.
+    if args.finalize {
+        for prerelease in document.take_prereleases(&new_version) {
+            release.absorb(prerelease);
+        }
+    }
+    for (section, notes) in grouped {
+        for note in notes {
+            release.add_note(section.clone(), note);
+        }
+    }
+    release.dedupe_notes();
 
     document
         .upsert_release(release, args.override_existing)
@@ -210,9 +1030,164 @@ async fn command_release(args: ReleaseArgs) -> Result<()> {
         .validate(false)
         .map_err(|issue| ChangelogError::InvalidArguments(issue.message()))?;
 
-    write_file(&args.file, &document.to_markdown())?;
-    println!("Updated {}", args.file.display());
-    Ok(())
+    if !args.no_links {
+        let remote = repo
+            .remote_url("origin")
+            .map_err(|e| ChangelogError::GitError(e.to_string()))?;
+        document.compare = remote
+            .and_then(|url| Forge::from_remote(&url, None))
+            .map(|forge| CompareLinks {
+                forge,
+                tag_prefix: target.tag_prefix.clone(),
+            });
+    }
+
+    write_document(
+        &target.file,
+        &document,
+        document_format(&target.file),
+        args.template.as_deref(),
+    )?;
+    println!("Updated {}", target.file.display());
+
+    let mut files = vec![target.file.clone()];
+    let bumped = bump_version_files(&target_version_files(args, target)?, &new_version)
+        .map_err(|e| ChangelogError::Other(e.to_string()))?;
+    for path in bumped {
+        println!("Set version {} in {}", new_version, path.display());
+        files.push(path);
+    }
+
+    let notes = document
+        .releases
+        .iter()
+        .find(|release| release.version.semver() == Some(&new_version))
+        .map(|release| release.body_markdown().trim_end().to_string())
+        .unwrap_or_default();
+    Ok(Some(ReleasedEntry {
+        crate_name: target.crate_name.clone(),
+        version: new_version,
+        tag,
+        files,
+        notes,
+    }))
+}
+
+/// Current version from the first version file that has one, for
+/// changelogs without releases.
+fn version_file_version(args: &ReleaseArgs, target: &ReleaseTarget) -> Result<Option<Version>> {
+    for file in &target_version_files(args, target)? {
+        let version = file
+            .read_version()
+            .map_err(|e| ChangelogError::Other(e.to_string()))?;
+        if version.is_some() {
+            return Ok(version);
+        }
+    }
+    Ok(None)
+}
+
+/// Files whose version `release` sets: the configured ones, or for a
+/// workspace crate its own `Cargo.toml` plus the workspace `Cargo.lock`.
+/// Crates inheriting `version.workspace = true` cannot be versioned on
+/// their own and are rejected.
+fn target_version_files(args: &ReleaseArgs, target: &ReleaseTarget) -> Result<Vec<VersionFile>> {
+    let Some(located) = &target.member else {
+        return Ok(args.version_files.clone());
+    };
+    let manifest = VersionFile::new(located.member.path.join("Cargo.toml"), None);
+    let own_version = manifest
+        .read_version()
+        .map_err(|e| ChangelogError::Other(e.to_string()))?;
+    if own_version.is_none() {
+        return Err(ChangelogError::InvalidArguments(format!(
+            "crate {} inherits its version from the workspace, but --workspace releases \
+             each crate with its own version; set `version` in {} or release without --workspace",
+            located.member.name,
+            manifest.path.display()
+        )));
+    }
+    let mut files = vec![manifest];
+    let lock = PathBuf::from("Cargo.lock");
+    if lock.is_file() {
+        files.push(VersionFile::new(lock, None));
+    }
+    Ok(files)
+}
+
+/// Workspace crate located both from the current directory and from the
+/// repository root, since git reports paths relative to the latter.
+#[derive(Debug)]
+struct LocatedMember {
+    member: WorkspaceMember,
+    repo_path: PathBuf,
+    /// Members nested inside this one; their files are not ours.
+    nested: Vec<PathBuf>,
+}
+
+fn workspace_members(repo: &Git2Repository) -> Result<Vec<LocatedMember>> {
+    let members =
+        detect_workspace(Path::new(".")).map_err(|e| ChangelogError::Other(e.to_string()))?;
+    if members.is_empty() {
+        return Err(ChangelogError::WorkspaceNotFound(
+            "Cargo.toml in the current directory has no [workspace] members".to_string(),
+        ));
+    }
+
+    let base = repo_base(repo)?;
+    Ok(members
+        .iter()
+        .map(|member| LocatedMember {
+            repo_path: base.join(&member.path),
+            nested: members
+                .iter()
+                .filter(|other| other.path != member.path && other.path.starts_with(&member.path))
+                .map(|other| base.join(&other.path))
+                .collect(),
+            member: member.clone(),
+        })
+        .collect())
+}
+
+fn repo_base(repo: &Git2Repository) -> Result<PathBuf> {
+    let cwd = std::env::current_dir()?.canonicalize()?;
+    let root = match repo.workdir() {
+        Some(workdir) => workdir.canonicalize()?,
+        None => cwd.clone(),
+    };
+    Ok(cwd
+        .strip_prefix(&root)
+        .unwrap_or(Path::new(""))
+        .to_path_buf())
+}
+
+fn repo_pathspecs(base: &Path, specs: &[String]) -> Vec<String> {
+    specs
+        .iter()
+        .map(|spec| {
+            let spec = spec.trim_start_matches("./");
+            base.join(spec).to_string_lossy().into_owned()
+        })
+        .collect()
+}
+
+impl LocatedMember {
+    /// Restricts `range` to commits changing files of this crate, leaving
+    /// out nested members.
+    fn narrow(&self, range: &CommitRange) -> CommitRange {
+        let mut exclude_paths: Vec<String> = self
+            .nested
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        exclude_paths.extend(range.exclude_paths.iter().cloned());
+
+        CommitRange {
+            include_paths: vec![self.repo_path.to_string_lossy().into_owned()],
+            exclude_paths,
+            ..range.clone()
+        }
+    }
 }
 
 fn command_show(
@@ -220,12 +1195,24 @@ fn command_show(
     version: Option<&str>,
     range: Option<&str>,
     converge: bool,
+    format: Option<&str>,
+    site_url: Option<&str>,
+    template: Option<&Path>,
 ) -> Result<()> {
+    let format = match format {
+        Some(format) => parse_document_format(format)?,
+        None => document_format(file),
+    };
+    if converge && !format.is_markup() {
+        return Err(ChangelogError::InvalidArguments(
+            "--converge is only supported with --format markdown, asciidoc or rst".to_string(),
+        ));
+    }
     let document = load_or_scaffold(file)?;
 
     let mut selected: Vec<Release> = if let Some(raw) = version {
-        let target =
-            Version::parse(raw).map_err(|_| ChangelogError::VersionParseError(raw.to_string()))?;
+        let target = ReleaseVersion::parse(raw)
+            .map_err(|_| ChangelogError::VersionParseError(raw.to_string()))?;
         document
             .releases
             .iter()
@@ -246,7 +1233,12 @@ fn command_show(
         document
             .releases
             .iter()
-            .filter(|release| release.version >= low && release.version <= high)
+            .filter(|release| {
+                release
+                    .version
+                    .semver()
+                    .is_some_and(|version| *version >= low && *version <= high)
+            })
             .cloned()
             .collect()
     } else {
@@ -263,19 +1255,41 @@ fn command_show(
 
     if converge {
         let converged = converge_releases(&selected);
-        println!("{}", converged.to_markdown());
+        let rendered = render_document(&converged, format, template)?;
+        println!("{}", rendered);
     } else {
+        let labels: Vec<String> = selected.iter().map(|r| r.version.to_string()).collect();
         let doc = ChangelogDocument {
             title: document.title,
+            preamble: document.preamble,
+            links: document
+                .links
+                .into_iter()
+                .filter(|link| labels.iter().any(|l| l.eq_ignore_ascii_case(&link.label)))
+                .collect(),
             releases: selected,
+            compare: None,
+        };
+        let rendered = match format {
+            DocumentFormat::Atom => feed::atom(&doc, site_url),
+            DocumentFormat::Rss => {
+                let site_url = site_url.ok_or_else(|| {
+                    ChangelogError::InvalidArguments(
+                        "--format rss requires --site-url; RSS channels must link to the changelog page"
+                            .to_string(),
+                    )
+                })?;
+                feed::rss(&doc, site_url)
+            }
+            _ => render_document(&doc, format, template)?,
         };
-        println!("{}", doc.to_markdown());
+        println!("{}", rendered);
     }
 
     Ok(())
 }
 
-fn command_remove(file: &Path, version: &str, yes: bool) -> Result<()> {
+fn command_remove(file: &Path, version: &str, yes: bool, template: Option<&Path>) -> Result<()> {
     if !yes {
         return Err(ChangelogError::InvalidArguments(
             "remove requires --yes to apply file changes".to_string(),
@@ -283,7 +1297,7 @@ fn command_remove(file: &Path, version: &str, yes: bool) -> Result<()> {
     }
 
     let mut document = load_or_scaffold(file)?;
-    let target = Version::parse(version)
+    let target = ReleaseVersion::parse(version)
         .map_err(|_| ChangelogError::VersionParseError(version.to_string()))?;
 
     if !document.remove_version(&target) {
@@ -293,11 +1307,209 @@ fn command_remove(file: &Path, version: &str, yes: bool) -> Result<()> {
         )));
     }
 
-    write_file(file, &document.to_markdown())?;
+    write_document(file, &document, document_format(file), template)?;
     println!("Removed release {} from {}", target, file.display());
     Ok(())
 }
 
+fn command_templates_list() -> Result<()> {
+    let registry = TemplateRegistry::load().map_err(|e| ChangelogError::template(e.to_string()))?;
+    let width = registry.names().map(str::len).max().unwrap_or(0);
+    for name in registry.names() {
+        let origin = match registry.origin(name) {
+            Some(TemplateOrigin::BuiltIn(description)) => format!("built-in: {}", description),
+            Some(TemplateOrigin::File(path)) => path.display().to_string(),
+            None => continue,
+        };
+        println!("{:width$}  {}", name, origin, width = width);
+    }
+    Ok(())
+}
+
+fn command_templates_show(name: &str) -> Result<()> {
+    let registry = TemplateRegistry::load().map_err(|e| ChangelogError::template(e.to_string()))?;
+    let source = registry.source(name).ok_or_else(|| {
+        ChangelogError::InvalidArguments(format!(
+            "unknown template '{}'; see `templates list`",
+            name
+        ))
+    })?;
+    println!("{}", source.trim_end());
+    Ok(())
+}
+
+/// Prints the parser that matched each message and the notes it yields.
+fn command_test_parser(messages: &[String], mapping: &TypeMapping, settings: &Settings) {
+    for (index, message) in messages.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let commit = Commit::new("", message);
+        println!("{}", commit.message);
+        let commit = mapping.preprocess(vec![commit]).remove(0);
+        if !mapping.preprocessors.is_empty() {
+            println!("  preprocessed: {}", commit.message);
+        }
+
+        if should_ignore_commit(&commit.message)
+            || has_ignore_marker(&commit.message, &settings.ignore_markers)
+        {
+            println!("  skipped: ignore marker");
+            continue;
+        }
+        match mapping.parsers.parse(&commit.message) {
+            Some(parsed) => {
+                println!("  matched: rule {} `{}`", parsed.rule, parsed.pattern);
+                if parsed.skip {
+                    println!("  skipped: rule sets skip");
+                    continue;
+                }
+            }
+            None if parse_conventional_header(&commit.message).is_some() => {
+                println!("  matched: conventional commit")
+            }
+            None => println!("  matched: nothing"),
+        }
+
+        let notes = mapping.postprocess(collect_notes(
+            std::slice::from_ref(&commit),
+            &mapping.types,
+            &mapping.parsers,
+        ));
+        for (section, notes) in group_notes(&notes, ScopeStyle::Inline) {
+            for note in notes {
+                println!("  {}: {}", section, note);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PublishArgs {
+    version: Option<String>,
+    file: PathBuf,
+    tag_prefix: String,
+    forge: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    dry_run: bool,
+    github_api_url: String,
+    gitlab_api_url: Option<String>,
+}
+
+async fn command_publish(args: PublishArgs) -> Result<()> {
+    let document = load_or_scaffold(&args.file)?;
+    let version = match &args.version {
+        Some(raw) => {
+            Version::parse(raw).map_err(|_| ChangelogError::VersionParseError(raw.to_string()))?
+        }
+        None => document.latest_version().cloned().ok_or_else(|| {
+            ChangelogError::InvalidArguments(format!(
+                "{} has no released version to publish",
+                args.file.display()
+            ))
+        })?,
+    };
+    let release = document
+        .releases
+        .iter()
+        .find(|release| release.version.semver() == Some(&version))
+        .ok_or_else(|| {
+            ChangelogError::InvalidArguments(format!(
+                "release {} was not found in {}",
+                version,
+                args.file.display()
+            ))
+        })?;
+
+    let kind = match &args.forge {
+        Some(raw) => Some(ForgeKind::parse(raw).ok_or_else(|| {
+            ChangelogError::InvalidArguments("--forge must be one of: github, gitlab".to_string())
+        })?),
+        None => None,
+    };
+    let repo = Git2Repository::open(".").map_err(|e| ChangelogError::GitError(e.to_string()))?;
+    let forge = repo
+        .remote_url("origin")
+        .map_err(|e| ChangelogError::GitError(e.to_string()))?
+        .and_then(|url| Forge::from_remote(&url, kind))
+        .ok_or_else(|| {
+            ChangelogError::InvalidArguments(
+                "cannot detect the forge from the `origin` remote; pass --forge".to_string(),
+            )
+        })?;
+
+    let tag = format!("{}{}", args.tag_prefix, version);
+    let payload = ForgeRelease {
+        tag: tag.clone(),
+        name: tag.clone(),
+        body: release.body_markdown().trim_end().to_string(),
+        draft: args.draft,
+        // GitLab has no pre-release flag, so only GitHub infers it.
+        prerelease: args.prerelease || (forge.kind == ForgeKind::GitHub && !version.pre.is_empty()),
+    };
+
+    if forge.kind == ForgeKind::GitLab && (payload.draft || payload.prerelease) {
+        return Err(ChangelogError::InvalidArguments(
+            "GitLab releases have no draft or pre-release state; drop --draft and --prerelease"
+                .to_string(),
+        ));
+    }
+
+    if args.dry_run {
+        let mut flags = String::new();
+        if payload.draft {
+            flags.push_str(" (draft)");
+        }
+        if payload.prerelease {
+            flags.push_str(" (pre-release)");
+        }
+        println!(
+            "Would publish {} release {} for {}{}\n\n{}",
+            forge.kind,
+            tag,
+            forge.project_path(),
+            flags,
+            payload.body
+        );
+        return Ok(());
+    }
+
+    let project = forge.project_path();
+    match forge.kind {
+        ForgeKind::GitHub => {
+            let client =
+                GitHubClient::new(&args.github_api_url, std::env::var("GITHUB_TOKEN").ok());
+            let (published, created) = client
+                .publish_release(project, &payload)
+                .await
+                .map_err(|e| ChangelogError::GitHubApiError(e.to_string()))?;
+            let action = if created { "Created" } else { "Updated" };
+            println!("{} GitHub release {}: {}", action, tag, published.html_url);
+        }
+        ForgeKind::GitLab => {
+            let api_url = args
+                .gitlab_api_url
+                .clone()
+                .unwrap_or_else(|| format!("{}/api/v4", forge.web_root()));
+            let client = GitLabClient::new(api_url, std::env::var("GITLAB_TOKEN").ok());
+            let created = client
+                .publish_release(project, &payload)
+                .await
+                .map_err(|e| ChangelogError::NetworkError(e.to_string()))?;
+            let action = if created { "Created" } else { "Updated" };
+            println!("{} GitLab release {}", action, tag);
+        }
+        other => {
+            return Err(ChangelogError::UnsupportedFeature(format!(
+                "publishing releases to {} is not supported",
+                other
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn converge_releases(releases: &[Release]) -> ChangelogDocument {
     let mut doc = ChangelogDocument::scaffold();
     let mut merged = Release::new(Version::new(0, 0, 0));
@@ -312,33 +1524,36 @@ fn converge_releases(releases: &[Release]) -> ChangelogDocument {
         }
     }
 
-    merged.sections = crate::core::notes::dedupe_grouped_notes(&merged.sections);
+    merged.dedupe_notes();
     doc.releases.push(merged);
     doc
 }
 
-fn bump_version(mut version: Version, bump: &str) -> Result<Version> {
-    match bump {
-        "major" => {
-            version.major += 1;
-            version.minor = 0;
-            version.patch = 0;
-        }
-        "minor" => {
-            version.minor += 1;
-            version.patch = 0;
-        }
-        "patch" => {
-            version.patch += 1;
-        }
-        _ => {
-            return Err(ChangelogError::InvalidArguments(
-                "--bump must be one of: major, minor, patch".to_string(),
-            ));
-        }
-    }
+fn parse_document_format(input: &str) -> Result<DocumentFormat> {
+    DocumentFormat::parse(input).ok_or_else(|| {
+        ChangelogError::InvalidArguments(
+            "--format must be one of: markdown, asciidoc, rst, json, yaml, toml, html, atom, rss"
+                .to_string(),
+        )
+    })
+}
+
+fn parse_scope_style(input: &str) -> Result<ScopeStyle> {
+    ScopeStyle::parse(input).ok_or_else(|| {
+        ChangelogError::InvalidArguments(
+            "--scope-style must be one of: inline, grouped, none".to_string(),
+        )
+    })
+}
 
-    Ok(version)
+fn parse_bump_level(bump: &str) -> Result<BumpLevel> {
+    match BumpLevel::parse(bump) {
+        Some(level) if level != BumpLevel::None => Ok(level),
+        _ => Err(ChangelogError::InvalidArguments(
+            "--bump must be one of: major, minor, patch, premajor, preminor, prepatch, prerelease, auto"
+                .to_string(),
+        )),
+    }
 }
 
 fn load_or_scaffold(path: &Path) -> Result<ChangelogDocument> {
@@ -347,21 +1562,82 @@ fn load_or_scaffold(path: &Path) -> Result<ChangelogDocument> {
     }
 
     let content = fs::read_to_string(path)?;
-    ChangelogDocument::parse(&content).map_err(|issue| {
-        ChangelogError::InvalidArguments(format!(
-            "failed to parse {}: {}",
-            path.display(),
-            issue.message()
-        ))
+    ChangelogDocument::load(&content, document_format(path)).map_err(|e| {
+        ChangelogError::InvalidArguments(format!("failed to parse {}: {}", path.display(), e))
     })
 }
 
-fn read_or_create_scaffold(path: &Path) -> Result<String> {
-    if path.exists() {
-        return Ok(fs::read_to_string(path)?);
+/// Format of a changelog file, picked by extension (Markdown by default).
+fn document_format(path: &Path) -> DocumentFormat {
+    DocumentFormat::from_path(path).unwrap_or_default()
+}
+
+fn write_document(
+    path: &Path,
+    document: &ChangelogDocument,
+    format: DocumentFormat,
+    template: Option<&Path>,
+) -> Result<()> {
+    let mut content = render_document(document, format, template)?;
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    write_file(path, &content)
+}
+
+/// Renders `document` in `format`. Markdown, AsciiDoc and RST are laid out
+/// by the document template when one is given; its output must parse back
+/// to the same releases, so the file stays readable by `validate` and
+/// later runs.
+fn render_document(
+    document: &ChangelogDocument,
+    format: DocumentFormat,
+    template: Option<&Path>,
+) -> Result<String> {
+    let Some(template) = template.filter(|_| format.is_markup()) else {
+        return document.render(format).map_err(ChangelogError::Other);
+    };
+
+    let mut registry =
+        TemplateRegistry::load().map_err(|e| ChangelogError::template(e.to_string()))?;
+    let name = registry
+        .resolve(template)
+        .map_err(|e| ChangelogError::template(e.to_string()))?;
+    let markdown = registry
+        .render(&name, &DocumentContext::new(document))
+        .map_err(|e| ChangelogError::template(e.to_string()))?;
+    let markdown = markdown.trim_end().to_string() + "\n";
+
+    let reparsed = ChangelogDocument::parse(&markdown).map_err(|issue| {
+        ChangelogError::template(format!(
+            "template '{}' does not produce a valid changelog: {}",
+            name,
+            issue.message()
+        ))
+    })?;
+    let versions = |doc: &ChangelogDocument| -> Vec<String> {
+        doc.releases.iter().map(|r| r.version.to_string()).collect()
+    };
+    if versions(&reparsed) != versions(document) {
+        return Err(ChangelogError::template(format!(
+            "template '{}' must render one release heading per release ({}), found ({})",
+            name,
+            versions(document).join(", "),
+            versions(&reparsed).join(", ")
+        )));
+    }
+    // Anything the template leaves out would be gone for good once the
+    // output is written back to the changelog.
+    for (release, rendered) in document.releases.iter().zip(&reparsed.releases) {
+        if let Some(difference) = release.content_difference(rendered) {
+            return Err(ChangelogError::template(format!(
+                "template '{}' does not preserve release {}: {}",
+                name, release.version, difference
+            )));
+        }
     }
 
-    Ok(ChangelogDocument::scaffold().to_markdown())
+    Ok(format.render_markdown(&markdown))
 }
 
 fn write_file(path: &Path, content: &str) -> Result<()> {
@@ -376,12 +1652,88 @@ fn write_file(path: &Path, content: &str) -> Result<()> {
 
 #[derive(Debug, Default, Deserialize)]
 struct TypeMapping {
-    types: Option<BTreeMap<String, String>>,
+    #[serde(default)]
+    types: BTreeMap<String, String>,
+    #[serde(default)]
+    bump: BTreeMap<String, String>,
+    /// Compiled `[[commit_parsers]]` from the config.
+    #[serde(skip)]
+    parsers: CommitParsers,
+    #[serde(skip)]
+    preprocessors: TextProcessors,
+    #[serde(skip)]
+    postprocessors: TextProcessors,
 }
 
-fn load_type_mapping(path: Option<&Path>) -> Result<BTreeMap<String, String>> {
+impl TypeMapping {
+    fn bump_rules(&self) -> Result<BTreeMap<String, BumpLevel>> {
+        let mut rules = BTreeMap::new();
+        for (kind, raw) in &self.bump {
+            let level = BumpLevel::parse(raw).ok_or_else(|| {
+                ChangelogError::InvalidArguments(format!(
+                    "invalid bump level '{}' for '{}'; use major, minor, patch or none",
+                    raw, kind
+                ))
+            })?;
+            rules.insert(kind.clone(), level);
+        }
+        Ok(rules)
+    }
+
+    /// Commits re-read after `[[commit_preprocessors]]` rewrote their
+    /// messages.
+    fn preprocess(&self, commits: Vec<Commit>) -> Vec<Commit> {
+        if self.preprocessors.is_empty() {
+            return commits;
+        }
+        commits
+            .into_iter()
+            .map(|commit| {
+                let message = self.preprocessors.apply(&commit.raw_message());
+                commit.with_message(&message)
+            })
+            .collect()
+    }
+
+    /// Notes with `[[note_postprocessors]]` applied to their text.
+    fn postprocess(&self, mut notes: Vec<Note>) -> Vec<Note> {
+        for note in &mut notes {
+            note.text = self.postprocessors.apply(&note.text);
+        }
+        notes
+    }
+}
+
+/// `[commit_types]` and `[bump]` from the config, overridden entry by entry
+/// by the `--map` file, plus the config's commit parsers and text
+/// processors.
+fn load_mapping(settings: &Settings, path: Option<&Path>) -> Result<TypeMapping> {
+    let overrides = load_mapping_file(path)?;
+    let mut mapping = TypeMapping {
+        types: settings.commit_types.clone(),
+        bump: settings.bump.clone(),
+        parsers: CommitParsers::new(&settings.commit_parsers)
+            .map_err(ChangelogError::RegexError)?,
+        preprocessors: TextProcessors::new(&settings.commit_preprocessors, "commit preprocessor")
+            .map_err(ChangelogError::RegexError)?,
+        postprocessors: TextProcessors::new(&settings.note_postprocessors, "note postprocessor")
+            .map_err(ChangelogError::RegexError)?,
+    };
+    mapping.types.extend(overrides.types);
+    mapping.bump.extend(overrides.bump);
+    Ok(mapping)
+}
+
+/// Drops commits carrying one of the configured extra ignore markers; the
+/// built-in markers are handled when notes are collected.
+fn without_ignored(mut commits: Vec<Commit>, settings: &Settings) -> Vec<Commit> {
+    commits.retain(|commit| !has_ignore_marker(&commit.message, &settings.ignore_markers));
+    commits
+}
+
+fn load_mapping_file(path: Option<&Path>) -> Result<TypeMapping> {
     let Some(path) = path else {
-        return Ok(BTreeMap::new());
+        return Ok(TypeMapping::default());
     };
 
     let content = fs::read_to_string(path)?;
@@ -402,23 +1754,41 @@ fn load_type_mapping(path: Option<&Path>) -> Result<BTreeMap<String, String>> {
         }
     };
 
-    Ok(parsed.types.unwrap_or_default())
+    Ok(parsed)
 }
 
-fn render_generated_sections(
-    grouped: BTreeMap<String, Vec<String>>,
-    template: Option<&Path>,
-) -> anyhow::Result<String> {
-    if let Some(path) = template {
-        let data = serde_json::json!({
-            "sections": grouped
-                .iter()
-                .map(|(k, v)| serde_json::json!({"name": k, "notes": v}))
-                .collect::<Vec<_>>()
-        });
-        return crate::infrastructure::templates::render(path.to_str(), &data);
+/// Version, previous version, compare link and repository of a `generate`
+/// range. Refs that are not `<tag prefix><semver>` leave the version
+/// `Unreleased` and the previous version unset.
+fn describe_range(
+    context: &mut TemplateContext,
+    args: &GenerateArgs,
+    settings: &Settings,
+    forge: Option<&Forge>,
+) {
+    let version_of = |reference: &str| {
+        let name = reference
+            .strip_prefix(settings.tag_prefix.as_str())
+            .unwrap_or(reference);
+        Version::parse(name).ok().map(|_| name.to_string())
+    };
+
+    if let Some(version) = [&args.milestone, &args.specific, &args.until]
+        .into_iter()
+        .flatten()
+        .find_map(|reference| version_of(reference))
+    {
+        context.version = version;
     }
+    context.previous_version = args.since.as_deref().and_then(version_of);
+    if let (Some(forge), Some(since)) = (forge, &args.since) {
+        context.compare_url =
+            Some(forge.compare_url(since, args.until.as_deref().unwrap_or("HEAD")));
+    }
+    context.repository = forge.map(TemplateRepository::from);
+}
 
+fn render_generated_sections(grouped: BTreeMap<String, Vec<String>>) -> String {
     let mut out = String::new();
     for (section, notes) in grouped {
         out.push_str("### ");
@@ -436,5 +1806,5 @@ fn render_generated_sections(
         out.push_str("### Other\n- No user-facing changes detected\n");
     }
 
-    Ok(out.trim_end().to_string() + "\n")
+    out.trim_end().to_string() + "\n"
 }
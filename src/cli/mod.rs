@@ -8,6 +8,9 @@ use std::path::PathBuf;
     about = "Generate and maintain changelogs from git history"
 )]
 pub struct Cli {
+    /// Config file to use instead of discovering `changelog.toml`.
+    #[arg(long, global = true, env = "CHANGELOGGEN_CONFIG")]
+    pub config: Option<PathBuf>,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -15,20 +18,20 @@ pub struct Cli {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     New {
-        #[arg(long, default_value = "CHANGELOG.md")]
-        file: PathBuf,
-        #[arg(long, default_value = "markdown")]
-        format: String,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
+        #[arg(long)]
+        format: Option<String>,
     },
     Validate {
-        #[arg(long, default_value = "CHANGELOG.md")]
-        file: PathBuf,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
         #[arg(long)]
         strict: bool,
     },
     Generate {
-        #[arg(long, default_value = "CHANGELOG.md")]
-        file: PathBuf,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
         #[arg(long)]
         since: Option<String>,
         #[arg(long)]
@@ -45,40 +48,162 @@ pub enum Commands {
         output: Option<PathBuf>,
         #[arg(long)]
         map: Option<PathBuf>,
+        #[arg(long = "scope")]
+        scopes: Vec<String>,
+        #[arg(long = "exclude-scope")]
+        exclude_scopes: Vec<String>,
+        #[arg(long, default_value = "inline")]
+        scope_style: String,
+        #[arg(long = "path", conflicts_with = "workspace")]
+        paths: Vec<String>,
+        #[arg(long = "exclude-path")]
+        exclude_paths: Vec<String>,
+        #[arg(long, conflicts_with = "milestone")]
+        workspace: bool,
+        #[arg(long, env = "GITHUB_API_URL")]
+        github_api_url: Option<String>,
     },
     #[command(group(
         ArgGroup::new("versioning")
             .required(true)
-            .args(["version", "bump"])
+            .multiple(true)
+            .args(["version", "bump", "finalize"])
     ))]
     Release {
         #[arg(long)]
         version: Option<String>,
         #[arg(long)]
         bump: Option<String>,
-        #[arg(long, default_value = "CHANGELOG.md")]
-        file: PathBuf,
-        #[arg(long, default_value = "default")]
-        header: String,
+        #[arg(long, default_value = "rc")]
+        preid: String,
+        #[arg(long)]
+        build: Option<String>,
+        #[arg(long)]
+        finalize: bool,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
+        #[arg(long, env = "CHANGELOGGEN_HEADER")]
+        header: Option<String>,
         #[arg(long = "override")]
         override_existing: bool,
+        #[arg(long, env = "CHANGELOGGEN_TAG_PREFIX")]
+        tag_prefix: Option<String>,
+        #[arg(long)]
+        no_links: bool,
+        #[arg(long)]
+        map: Option<PathBuf>,
+        #[arg(long, default_value = "inline")]
+        scope_style: String,
+        #[arg(long)]
+        workspace: bool,
+        #[arg(long)]
+        commit: bool,
+        #[arg(long, requires = "commit")]
+        tag: bool,
+        #[arg(long)]
+        commit_message: Option<String>,
+        #[arg(long = "stage")]
+        stage: Vec<PathBuf>,
+        /// With `--workspace`, each crate's own `Cargo.toml` is bumped instead.
+        #[arg(long = "version-file", conflicts_with = "workspace")]
+        version_files: Vec<PathBuf>,
+        #[arg(long)]
+        allow_dirty: bool,
+        /// Document template (name or `.hbs` file) for the whole changelog.
+        #[arg(long, env = "CHANGELOGGEN_TEMPLATE")]
+        template: Option<PathBuf>,
     },
     Show {
-        #[arg(long, default_value = "CHANGELOG.md")]
-        file: PathBuf,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
         #[arg(long)]
         version: Option<String>,
         #[arg(long)]
         range: Option<String>,
         #[arg(long)]
         converge: bool,
+        #[arg(long)]
+        format: Option<String>,
+        /// URL the changelog page is published at, for feed links and ids.
+        #[arg(long)]
+        site_url: Option<String>,
+        /// Document template (name or `.hbs` file) for Markdown-family output.
+        #[arg(long, env = "CHANGELOGGEN_TEMPLATE")]
+        template: Option<PathBuf>,
+    },
+    /// Write the changelog as debian/changelog, RPM %changelog or AppStream releases.
+    Export {
+        #[arg(long)]
+        format: String,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
+        #[arg(long)]
+        output: Option<PathBuf>,
+        #[arg(long)]
+        package: Option<String>,
+        #[arg(long)]
+        maintainer: Option<String>,
+    },
+    /// Render a JSON, YAML or TOML changelog to Markdown.
+    Import {
+        input: PathBuf,
+        #[arg(long)]
+        format: Option<String>,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
+        #[arg(long, env = "CHANGELOGGEN_HEADER")]
+        header: Option<String>,
+        #[arg(long)]
+        strict: bool,
     },
     Remove {
         #[arg(long)]
         version: String,
-        #[arg(long, default_value = "CHANGELOG.md")]
-        file: PathBuf,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
         #[arg(long)]
         yes: bool,
     },
+    Publish {
+        #[arg(long)]
+        version: Option<String>,
+        #[arg(long, env = "CHANGELOGGEN_FILE")]
+        file: Option<PathBuf>,
+        #[arg(long, env = "CHANGELOGGEN_TAG_PREFIX")]
+        tag_prefix: Option<String>,
+        #[arg(long)]
+        forge: Option<String>,
+        #[arg(long)]
+        draft: bool,
+        #[arg(long)]
+        prerelease: bool,
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long, env = "GITHUB_API_URL")]
+        github_api_url: Option<String>,
+        #[arg(long, env = "CI_API_V4_URL")]
+        gitlab_api_url: Option<String>,
+    },
+    /// List or print the templates `generate --template` accepts by name.
+    Templates {
+        #[command(subcommand)]
+        command: TemplatesCommand,
+    },
+    /// Show how `[[commit_parsers]]` and the conventional commit parser
+    /// classify commit messages.
+    TestParser {
+        /// Full commit messages; the first line is the summary.
+        #[arg(required = true)]
+        messages: Vec<String>,
+        #[arg(long)]
+        map: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplatesCommand {
+    /// Built-in and user templates with where each comes from.
+    List,
+    /// Print the source of a template.
+    Show { name: String },
 }
@@ -1,17 +1,85 @@
 use crate::config::schema::Config;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use std::fs;
+use std::path::{Path, PathBuf};
 
+/// File names looked for in each directory, in order of preference.
+pub const CONFIG_FILE_NAMES: [&str; 3] = ["changelog.toml", "changelog.yaml", "changelog.yml"];
+
+/// Loads the configuration discovered from the current directory, or the
+/// defaults when there is none.
 pub fn load_config() -> Result<Config> {
-    if std::path::Path::new("changelog.toml").exists() {
-        let content = fs::read_to_string("changelog.toml")?;
-        return Ok(toml::from_str(&content)?);
+    match discover_config(&std::env::current_dir()?) {
+        Some(path) => load_config_file(&path),
+        None => Ok(Config::default()),
     }
+}
 
-    if std::path::Path::new("changelog.yaml").exists() {
-        let content = fs::read_to_string("changelog.yaml")?;
-        return Ok(serde_yaml::from_str(&content)?);
+/// Looks for a config file in `start` and its parents, stopping at the
+/// repository root (the first directory containing `.git`).
+pub fn discover_config(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        for name in CONFIG_FILE_NAMES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
     }
+    None
+}
+
+/// Parses a TOML or YAML config file, picked by extension.
+pub fn load_config_file(path: &Path) -> Result<Config> {
+    let content = fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => Ok(serde_yaml::from_str(&content)?),
+        Some("toml") => Ok(toml::from_str(&content)?),
+        other => Err(anyhow!(
+            "unsupported config extension '{}'; use .toml or .yaml",
+            other.unwrap_or_default()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(Config::default())
+    #[test]
+    fn discovers_config_in_parent_directories() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        fs::create_dir_all(root.join(".git")).expect("mkdir .git");
+        fs::create_dir_all(root.join("crates/core")).expect("mkdir crate");
+        fs::write(
+            root.join("changelog.toml"),
+            "[commit_types]\nfeat = \"Features\"\n\n[changelog]\ntag_prefix = \"release-\"\nignore_markers = [\"[no log]\"]\n",
+        )
+        .expect("write config");
+
+        let path = discover_config(&root.join("crates/core")).expect("config found");
+        assert_eq!(path, root.join("changelog.toml"));
+
+        let config = load_config_file(&path).expect("config parses");
+        assert_eq!(config.commit_types["feat"], "Features");
+        assert_eq!(config.changelog.tag_prefix.as_deref(), Some("release-"));
+        assert_eq!(
+            config.changelog.ignore_markers,
+            vec!["[no log]".to_string()]
+        );
+    }
+
+    #[test]
+    fn stops_at_repository_root() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        fs::write(root.join("changelog.toml"), "").expect("write config");
+        fs::create_dir_all(root.join("repo/.git")).expect("mkdir .git");
+
+        assert_eq!(discover_config(&root.join("repo")), None);
+    }
 }
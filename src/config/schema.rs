@@ -1,10 +1,39 @@
+use crate::core::commit_parser::ParserRule;
+use crate::core::processors::TextProcessor;
 use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 #[derive(Deserialize, Default, Debug, PartialEq)]
 pub struct Config {
     pub project: Option<Project>,
     pub notifications: Option<Notifications>,
     pub telemetry: Option<Telemetry>,
+    /// Commit type -> changelog section, e.g. `feat = "Features"`.
+    #[serde(default)]
+    pub commit_types: BTreeMap<String, String>,
+    /// Commit type (or `breaking`) -> bump level for `release --bump auto`.
+    #[serde(default)]
+    pub bump: BTreeMap<String, String>,
+    /// `[[commit_parsers]]` rules tried in order before conventional
+    /// commit parsing.
+    #[serde(default)]
+    pub commit_parsers: Vec<ParserRule>,
+    /// Replacements applied to commit messages before parsing.
+    #[serde(default)]
+    pub commit_preprocessors: Vec<TextProcessor>,
+    /// Replacements applied to note text, e.g. to link issue keys.
+    #[serde(default)]
+    pub note_postprocessors: Vec<TextProcessor>,
+    #[serde(default)]
+    pub changelog: ChangelogConfig,
+    #[serde(default)]
+    pub github: GitHubConfig,
+    /// Files whose version `release` updates.
+    #[serde(default)]
+    pub version_files: Vec<VersionFileConfig>,
+    #[serde(default)]
+    pub packaging: PackagingConfig,
 }
 
 #[derive(Deserialize, Debug, PartialEq)]
@@ -23,3 +52,49 @@ pub struct Notifications {
 pub struct Telemetry {
     pub enabled: bool,
 }
+
+/// `[changelog]` defaults for command flags.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+pub struct ChangelogConfig {
+    pub file: Option<PathBuf>,
+    pub header: Option<String>,
+    pub tag_prefix: Option<String>,
+    /// Message for `release --commit`; `{version}` and `{tag}` are replaced.
+    pub commit_message: Option<String>,
+    /// Extra markers that exclude a commit, on top of the built-in ones.
+    #[serde(default)]
+    pub ignore_markers: Vec<String>,
+    /// Document template for `release`, `remove` and `show`: a template
+    /// name or a `.hbs` path.
+    pub template: Option<PathBuf>,
+}
+
+/// `[github]` settings for milestone mode.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+pub struct GitHubConfig {
+    pub api_url: Option<String>,
+    /// Issue/PR label -> changelog section.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// `[packaging]` metadata for `export --format debian|rpm|appstream`.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+pub struct PackagingConfig {
+    /// Source package name; defaults to `[project] name`.
+    pub package: Option<String>,
+    /// `Full Name <email>`.
+    pub maintainer: Option<String>,
+    pub distribution: Option<String>,
+    pub urgency: Option<String>,
+    /// Debian revision / RPM release, `1` by default.
+    pub revision: Option<String>,
+}
+
+/// `[[version_files]]` entry; `pattern` is needed for files other than
+/// Cargo.toml, Cargo.lock, package.json, pyproject.toml and plain files.
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct VersionFileConfig {
+    pub path: PathBuf,
+    pub pattern: Option<String>,
+}
@@ -0,0 +1,175 @@
+use crate::config::schema::Config;
+use crate::core::commit_parser::ParserRule;
+use crate::core::packaging::Packaging;
+use crate::core::processors::TextProcessor;
+use crate::infrastructure::github::DEFAULT_API_URL;
+use crate::infrastructure::version_files::VersionFile;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_FILE: &str = "CHANGELOG.md";
+pub const DEFAULT_HEADER: &str = "default";
+pub const DEFAULT_TAG_PREFIX: &str = "v";
+pub const DEFAULT_COMMIT_MESSAGE: &str = "chore(release): {version}";
+
+/// Configuration file values merged over the built-in defaults. Commands
+/// apply their flags (and the matching environment variables) on top.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Config file the values came from, if any.
+    pub source: Option<PathBuf>,
+    /// Changelog path; relative paths from a config file are resolved
+    /// against its directory.
+    pub file: PathBuf,
+    pub header: String,
+    pub tag_prefix: String,
+    pub commit_message: String,
+    pub commit_types: BTreeMap<String, String>,
+    pub bump: BTreeMap<String, String>,
+    pub commit_parsers: Vec<ParserRule>,
+    pub commit_preprocessors: Vec<TextProcessor>,
+    pub note_postprocessors: Vec<TextProcessor>,
+    pub ignore_markers: Vec<String>,
+    pub github_api_url: String,
+    pub labels: BTreeMap<String, String>,
+    /// Version files, relative to the config file's directory.
+    pub version_files: Vec<VersionFile>,
+    pub packaging: Packaging,
+    /// Document template name or path; `None` uses the built-in layout.
+    pub template: Option<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::from_config(Config::default(), None)
+    }
+}
+
+impl Settings {
+    pub fn from_config(config: Config, source: Option<PathBuf>) -> Self {
+        let config_dir = config_dir(source.as_deref()).to_path_buf();
+        let version_files = config
+            .version_files
+            .into_iter()
+            .map(|file| VersionFile::new(config_dir.join(file.path), file.pattern))
+            .collect();
+
+        let defaults = Packaging::default();
+        let packaging = Packaging {
+            package: config
+                .packaging
+                .package
+                .or_else(|| config.project.as_ref().map(|project| project.name.clone())),
+            maintainer: config.packaging.maintainer,
+            distribution: config
+                .packaging
+                .distribution
+                .unwrap_or(defaults.distribution),
+            urgency: config.packaging.urgency.unwrap_or(defaults.urgency),
+            revision: config.packaging.revision.unwrap_or(defaults.revision),
+        };
+
+        let changelog = config.changelog;
+        Self {
+            source,
+            file: config_dir.join(
+                changelog
+                    .file
+                    .unwrap_or_else(|| PathBuf::from(DEFAULT_FILE)),
+            ),
+            header: changelog
+                .header
+                .unwrap_or_else(|| DEFAULT_HEADER.to_string()),
+            tag_prefix: changelog
+                .tag_prefix
+                .unwrap_or_else(|| DEFAULT_TAG_PREFIX.to_string()),
+            commit_message: changelog
+                .commit_message
+                .unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE.to_string()),
+            commit_types: config.commit_types,
+            bump: config.bump,
+            commit_parsers: config.commit_parsers,
+            commit_preprocessors: config.commit_preprocessors,
+            note_postprocessors: config.note_postprocessors,
+            ignore_markers: changelog.ignore_markers,
+            github_api_url: config
+                .github
+                .api_url
+                .unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+            labels: config.github.labels,
+            version_files,
+            packaging,
+            template: changelog
+                .template
+                .map(|template| template_path(&config_dir, template)),
+        }
+    }
+
+    /// Directory relative config paths are resolved against; empty without
+    /// a config file.
+    pub fn config_dir(&self) -> &Path {
+        config_dir(self.source.as_deref())
+    }
+}
+
+/// A template given as a path (with an extension or a directory) is
+/// relative to the config file; a bare name refers to a registered template.
+fn template_path(config_dir: &Path, template: PathBuf) -> PathBuf {
+    let is_path = template.extension().is_some() || template.components().count() > 1;
+    match is_path {
+        true => config_dir.join(template),
+        false => template,
+    }
+}
+
+fn config_dir(source: Option<&Path>) -> &Path {
+    source
+        .and_then(|path| path.parent())
+        .unwrap_or_else(|| Path::new(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::ChangelogConfig;
+
+    #[test]
+    fn resolves_paths_against_the_config_directory() {
+        let config = || Config {
+            changelog: ChangelogConfig {
+                file: Some(PathBuf::from("docs/CHANGES.md")),
+                ..ChangelogConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let settings = Settings::from_config(config(), Some(PathBuf::from("/repo/changelog.toml")));
+        assert_eq!(settings.file, PathBuf::from("/repo/docs/CHANGES.md"));
+
+        let settings = Settings::from_config(
+            Config::default(),
+            Some(PathBuf::from("/repo/changelog.toml")),
+        );
+        assert_eq!(settings.file, PathBuf::from("/repo/CHANGELOG.md"));
+        assert_eq!(settings.config_dir(), Path::new("/repo"));
+
+        assert_eq!(Settings::default().file, PathBuf::from(DEFAULT_FILE));
+
+        let with_template = |template: &str| Config {
+            changelog: ChangelogConfig {
+                template: Some(PathBuf::from(template)),
+                ..ChangelogConfig::default()
+            },
+            ..Config::default()
+        };
+        let source = || Some(PathBuf::from("/repo/changelog.toml"));
+        assert_eq!(
+            Settings::from_config(with_template("templates/release.hbs"), source()).template,
+            Some(PathBuf::from("/repo/templates/release.hbs"))
+        );
+        assert_eq!(
+            Settings::from_config(with_template("document"), source()).template,
+            Some(PathBuf::from("document"))
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use crate::core::commit_parser::CommitParsers;
+use crate::core::notes::{parse_conventional_header, should_ignore_commit, BREAKING_KIND};
+use crate::domain::commit::Commit;
+use semver::{BuildMetadata, Prerelease, Version};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BumpLevel {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+/// Outcome of `infer_bump`, with a human-readable explanation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BumpDecision {
+    pub level: BumpLevel,
+    pub reason: String,
+}
+
+impl BumpLevel {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "none" => Some(BumpLevel::None),
+            "patch" => Some(BumpLevel::Patch),
+            "minor" => Some(BumpLevel::Minor),
+            "major" => Some(BumpLevel::Major),
+            _ => None,
+        }
+    }
+
+    /// Applies the bump. A pre-release of the target version is released
+    /// as-is, e.g. `2.0.0-rc.1` bumped by major becomes `2.0.0`. Build
+    /// metadata is always dropped.
+    pub fn apply(self, mut version: Version) -> Version {
+        let was_prerelease = !version.pre.is_empty();
+        version.pre = Prerelease::EMPTY;
+        version.build = BuildMetadata::EMPTY;
+
+        match self {
+            BumpLevel::None => {}
+            BumpLevel::Major => {
+                if !(was_prerelease && version.minor == 0 && version.patch == 0) {
+                    version.major += 1;
+                }
+                version.minor = 0;
+                version.patch = 0;
+            }
+            BumpLevel::Minor => {
+                if !(was_prerelease && version.patch == 0) {
+                    version.minor += 1;
+                }
+                version.patch = 0;
+            }
+            BumpLevel::Patch => {
+                if !was_prerelease {
+                    version.patch += 1;
+                }
+            }
+        }
+        version
+    }
+
+    /// Bumps to a pre-release. With a level (`premajor`, `preminor`,
+    /// `prepatch`) the version is bumped first and gets `<preid>.1`. Without
+    /// one (`prerelease`), an existing `<preid>.N` is incremented, another
+    /// pre-release id restarts at `<preid>.1` and a final version is
+    /// patch-bumped first.
+    pub fn apply_prerelease(
+        level: Option<BumpLevel>,
+        mut version: Version,
+        preid: &str,
+    ) -> Result<Version, semver::Error> {
+        version.build = BuildMetadata::EMPTY;
+
+        let next = match level {
+            Some(level) => {
+                version.pre = Prerelease::EMPTY;
+                version = level.apply(version);
+                1
+            }
+            None if version.pre.is_empty() => {
+                version = BumpLevel::Patch.apply(version);
+                1
+            }
+            None => {
+                let current = version.pre.as_str();
+                match current.strip_prefix(preid) {
+                    Some("") => 1,
+                    Some(rest) => rest
+                        .strip_prefix('.')
+                        .and_then(|n| n.parse::<u64>().ok())
+                        .map_or(1, |n| n + 1),
+                    None => 1,
+                }
+            }
+        };
+
+        version.pre = Prerelease::new(&format!("{}.{}", preid, next))?;
+        Ok(version)
+    }
+}
+
+impl fmt::Display for BumpLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BumpLevel::None => "none",
+            BumpLevel::Patch => "patch",
+            BumpLevel::Minor => "minor",
+            BumpLevel::Major => "major",
+        })
+    }
+}
+
+/// Level a single commit asks for, or `None` when a `[[commit_parsers]]`
+/// rule skips it. `rules` maps commit types (and `breaking`) to levels and
+/// overrides the defaults: breaking changes are major, `feat` is minor and
+/// everything else is patch. Rules that set a section without a `type`
+/// capture are looked up by section name; `Added` defaults to minor.
+pub fn commit_bump(
+    commit: &Commit,
+    rules: &BTreeMap<String, BumpLevel>,
+    parsers: &CommitParsers,
+) -> Option<BumpLevel> {
+    let (kind, breaking) = match parsers.parse(&commit.message) {
+        Some(parsed) if parsed.skip => return None,
+        Some(parsed) => {
+            let breaking = parsed.breaking || parsed.section.as_deref() == Some("Breaking Changes");
+            (parsed.kind.or(parsed.section), breaking)
+        }
+        None => match parse_conventional_header(&commit.message) {
+            Some(header) => (Some(header.kind), header.breaking),
+            None => (None, false),
+        },
+    };
+
+    if commit.breaking || breaking {
+        return Some(
+            rules
+                .get(BREAKING_KIND)
+                .copied()
+                .unwrap_or(BumpLevel::Major),
+        );
+    }
+
+    let Some(kind) = kind else {
+        return Some(BumpLevel::Patch);
+    };
+
+    if let Some(level) = rules.get(&kind) {
+        return Some(*level);
+    }
+
+    Some(match kind.as_str() {
+        "feat" | "Added" => BumpLevel::Minor,
+        _ => BumpLevel::Patch,
+    })
+}
+
+/// Picks the bump for `commits` relative to `current`. While the major
+/// version is 0, a major bump is downgraded to minor.
+pub fn infer_bump(
+    commits: &[Commit],
+    rules: &BTreeMap<String, BumpLevel>,
+    parsers: &CommitParsers,
+    current: &Version,
+) -> BumpDecision {
+    let mut level = BumpLevel::None;
+    let mut cause: Option<&Commit> = None;
+    let mut considered = 0usize;
+
+    for commit in commits {
+        if should_ignore_commit(&commit.message) {
+            continue;
+        }
+        let Some(wanted) = commit_bump(commit, rules, parsers) else {
+            continue;
+        };
+        considered += 1;
+
+        if wanted > level {
+            level = wanted;
+            cause = Some(commit);
+        }
+    }
+
+    let Some(cause) = cause else {
+        return BumpDecision {
+            level: BumpLevel::None,
+            reason: format!("none of {} commit(s) require a release", considered),
+        };
+    };
+
+    let initial_development = level == BumpLevel::Major && current.major == 0;
+    if initial_development {
+        level = BumpLevel::Minor;
+    }
+
+    let short = cause.hash.get(..7).unwrap_or(&cause.hash);
+    let mut reason = format!(
+        "{} bump from {} commit(s); highest: {} \"{}\"",
+        level, considered, short, cause.message
+    );
+    if initial_development {
+        reason.push_str(" (breaking changes bump minor while the major version is 0)");
+    }
+
+    BumpDecision { level, reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commits(messages: &[&str]) -> Vec<Commit> {
+        messages
+            .iter()
+            .enumerate()
+            .map(|(i, m)| Commit::new(format!("{:040}", i), m))
+            .collect()
+    }
+
+    #[test]
+    fn picks_highest_level() {
+        let rules = BTreeMap::new();
+        let current = Version::new(1, 2, 3);
+
+        let decision = infer_bump(
+            &commits(&["fix: a", "docs: b"]),
+            &rules,
+            &CommitParsers::default(),
+            &current,
+        );
+        assert_eq!(decision.level, BumpLevel::Patch);
+
+        let decision = infer_bump(
+            &commits(&["fix: a", "feat: b"]),
+            &rules,
+            &CommitParsers::default(),
+            &current,
+        );
+        assert_eq!(decision.level, BumpLevel::Minor);
+        assert!(decision.reason.contains("\"feat: b\""));
+
+        let decision = infer_bump(
+            &commits(&["feat: a", "fix: b\n\nBREAKING CHANGE: c"]),
+            &rules,
+            &CommitParsers::default(),
+            &current,
+        );
+        assert_eq!(decision.level, BumpLevel::Major);
+    }
+
+    #[test]
+    fn bumps_prereleases() {
+        let v = |s: &str| Version::parse(s).expect("version");
+
+        assert_eq!(
+            BumpLevel::apply_prerelease(Some(BumpLevel::Major), v("1.4.2"), "rc").expect("bump"),
+            v("2.0.0-rc.1")
+        );
+        assert_eq!(
+            BumpLevel::apply_prerelease(None, v("2.0.0-rc.1"), "rc").expect("bump"),
+            v("2.0.0-rc.2")
+        );
+        assert_eq!(
+            BumpLevel::apply_prerelease(None, v("2.0.0-beta.3"), "rc").expect("bump"),
+            v("2.0.0-rc.1")
+        );
+        assert_eq!(
+            BumpLevel::apply_prerelease(None, v("1.4.2+build.5"), "alpha").expect("bump"),
+            v("1.4.3-alpha.1")
+        );
+        assert_eq!(BumpLevel::Major.apply(v("2.0.0-rc.2")), v("2.0.0"));
+        assert_eq!(BumpLevel::Minor.apply(v("2.0.0-rc.2")), v("2.0.0"));
+        assert_eq!(BumpLevel::Patch.apply(v("2.0.1-rc.2")), v("2.0.1"));
+        assert_eq!(BumpLevel::Minor.apply(v("2.0.1")), v("2.1.0"));
+    }
+
+    #[test]
+    fn applies_rules_and_zero_major_convention() {
+        let mut rules = BTreeMap::new();
+        rules.insert("docs".to_string(), BumpLevel::None);
+        rules.insert("perf".to_string(), BumpLevel::Minor);
+
+        let decision = infer_bump(
+            &commits(&["docs: a"]),
+            &rules,
+            &CommitParsers::default(),
+            &Version::new(1, 0, 0),
+        );
+        assert_eq!(decision.level, BumpLevel::None);
+
+        let decision = infer_bump(
+            &commits(&["perf: a"]),
+            &rules,
+            &CommitParsers::default(),
+            &Version::new(1, 0, 0),
+        );
+        assert_eq!(decision.level, BumpLevel::Minor);
+
+        let decision = infer_bump(
+            &commits(&["feat!: drop the major flag"]),
+            &rules,
+            &CommitParsers::default(),
+            &Version::new(0, 3, 1),
+        );
+        assert_eq!(decision.level, BumpLevel::Minor);
+        assert!(decision.reason.starts_with("minor bump from 1 commit(s)"));
+        assert!(decision.reason.contains("\"feat!: drop the major flag\""));
+        assert!(decision.reason.contains("major version is 0"));
+    }
+
+    #[test]
+    fn follows_commit_parser_rules() {
+        use crate::core::commit_parser::ParserRule;
+
+        let parsers = CommitParsers::new(&[
+            ParserRule {
+                pattern: "^:sparkles:".to_string(),
+                section: Some("Added".to_string()),
+                ..ParserRule::default()
+            },
+            ParserRule {
+                pattern: "^:boom:".to_string(),
+                section: Some("Changed".to_string()),
+                ..ParserRule::default()
+            },
+            ParserRule {
+                pattern: r"^\[[A-Z]+-\d+\](?P<breaking>!)? ".to_string(),
+                section: Some("Fixed".to_string()),
+                ..ParserRule::default()
+            },
+            ParserRule {
+                pattern: "^feat: wip".to_string(),
+                skip: true,
+                ..ParserRule::default()
+            },
+        ])
+        .expect("valid rules");
+        let rules = BTreeMap::new();
+        let current = Version::new(1, 2, 3);
+        let infer = |messages: &[&str]| infer_bump(&commits(messages), &rules, &parsers, &current);
+
+        assert_eq!(infer(&[":sparkles: add export"]).level, BumpLevel::Minor);
+        assert_eq!(infer(&["[WEB-1] fix login"]).level, BumpLevel::Patch);
+        assert_eq!(infer(&["[WEB-2]! drop v1"]).level, BumpLevel::Major);
+
+        let skipped = infer(&["feat: wip search", "fix: a"]);
+        assert_eq!(skipped.level, BumpLevel::Patch);
+        assert!(skipped.reason.contains("from 1 commit(s)"));
+
+        let mut by_section = BTreeMap::new();
+        by_section.insert("Changed".to_string(), BumpLevel::Minor);
+        let decision = infer_bump(
+            &commits(&[":boom: new engine"]),
+            &by_section,
+            &parsers,
+            &current,
+        );
+        assert_eq!(decision.level, BumpLevel::Minor);
+    }
+}
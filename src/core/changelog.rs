@@ -1,29 +1,160 @@
+use crate::core::forge::Forge;
+use crate::core::markup::{AsciiDoc, MarkupBackend, ReStructuredText};
+use crate::core::notes::canonical_note_key;
+use crate::core::{feed, html};
 use chrono::Utc;
+use indexmap::IndexMap;
 use semver::Version;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Serializes to the shape described by `docs/changelog.schema.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChangelogDocument {
     pub title: String,
+    /// Free-form Markdown between the title and the first release.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub preamble: String,
+    #[serde(default)]
     pub releases: Vec<Release>,
+    /// Reference-style link definitions at the end of the file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<LinkDefinition>,
+    /// When set, `to_markdown` regenerates the compare link of every release.
+    #[serde(skip)]
+    pub compare: Option<CompareLinks>,
 }
 
+/// Settings for generating Keep-a-Changelog compare links.
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompareLinks {
+    pub forge: Forge,
+    pub tag_prefix: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Release {
-    pub version: Version,
+    pub version: ReleaseVersion,
+    #[serde(default)]
     pub date: Option<String>,
+    #[serde(skip)]
     pub header: HeaderFormat,
-    pub sections: BTreeMap<String, Vec<String>>,
+    /// Prose between the release heading and its first section.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Notes per section in document order; a note may span several lines,
+    /// including nested bullets, stored relative to its own bullet.
+    #[serde(default)]
+    pub sections: IndexMap<String, Vec<String>>,
+    /// Prose under a section heading that is not part of a note; written
+    /// above the section's notes.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub section_descriptions: BTreeMap<String, String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A `[label]: url` link definition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkDefinition {
+    pub label: String,
+    pub url: String,
+}
+
+/// Version of a release entry. `Unreleased` collects notes for the next
+/// release and always sorts above every SemVer release.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReleaseVersion {
+    Released(Version),
+    Unreleased,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum HeaderFormat {
+    #[default]
     Default,
     Plain,
     VersionOnly,
     Custom(String),
 }
 
+/// File formats a changelog can be read from or rendered to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocumentFormat {
+    #[default]
+    Markdown,
+    AsciiDoc,
+    Rst,
+    Json,
+    Yaml,
+    Toml,
+    /// Output only: a standalone page.
+    Html,
+    /// Output only: a feed with one entry per release.
+    Atom,
+    /// Output only: a feed with one entry per release.
+    Rss,
+}
+
+impl DocumentFormat {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "markdown" | "md" => Some(DocumentFormat::Markdown),
+            "asciidoc" | "adoc" => Some(DocumentFormat::AsciiDoc),
+            "rst" | "restructuredtext" => Some(DocumentFormat::Rst),
+            "json" => Some(DocumentFormat::Json),
+            "yaml" | "yml" => Some(DocumentFormat::Yaml),
+            "toml" => Some(DocumentFormat::Toml),
+            "html" | "htm" => Some(DocumentFormat::Html),
+            "atom" => Some(DocumentFormat::Atom),
+            "rss" => Some(DocumentFormat::Rss),
+            _ => None,
+        }
+    }
+
+    /// Format implied by the file extension, if it is a known one.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| Self::parse(&ext.to_ascii_lowercase()))
+    }
+
+    /// Whether a changelog written in this format can be read back, i.e.
+    /// it is not HTML or feed output.
+    pub fn is_readable(self) -> bool {
+        !matches!(
+            self,
+            DocumentFormat::Html | DocumentFormat::Atom | DocumentFormat::Rss
+        )
+    }
+
+    /// Whether the format is a changelog markup (Markdown, AsciiDoc, RST).
+    pub fn is_markup(self) -> bool {
+        matches!(
+            self,
+            DocumentFormat::Markdown | DocumentFormat::AsciiDoc | DocumentFormat::Rst
+        )
+    }
+
+    /// Translates a Markdown changelog into this format; Markdown and
+    /// non-markup formats are returned unchanged.
+    pub fn render_markdown(self, markdown: &str) -> String {
+        match self.markup() {
+            Some(markup) => markup.render_markdown(markdown),
+            None => markdown.to_string(),
+        }
+    }
+
+    /// The backend translating this markup from and to Markdown.
+    fn markup(self) -> Option<&'static dyn MarkupBackend> {
+        match self {
+            DocumentFormat::AsciiDoc => Some(&AsciiDoc),
+            DocumentFormat::Rst => Some(&ReStructuredText),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseIssue {
     pub line: usize,
@@ -45,7 +176,47 @@ impl ChangelogDocument {
     pub fn scaffold() -> Self {
         Self {
             title: "Changelog".to_string(),
+            preamble: String::new(),
             releases: Vec::new(),
+            links: Vec::new(),
+            compare: None,
+        }
+    }
+
+    /// Renders the document in `format`; structured formats follow
+    /// `docs/changelog.schema.json`.
+    pub fn render(&self, format: DocumentFormat) -> Result<String, String> {
+        match format {
+            DocumentFormat::Markdown => Ok(self.to_markdown()),
+            DocumentFormat::AsciiDoc | DocumentFormat::Rst => {
+                Ok(format.render_markdown(&self.to_markdown()))
+            }
+            DocumentFormat::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+            DocumentFormat::Yaml => serde_yaml::to_string(self).map_err(|e| e.to_string()),
+            DocumentFormat::Toml => toml::to_string(self).map_err(|e| e.to_string()),
+            DocumentFormat::Html => Ok(html::page(self)),
+            DocumentFormat::Atom => Ok(feed::atom(self, None)),
+            DocumentFormat::Rss => {
+                Err("rss output needs a site URL; use `show --format rss --site-url`".to_string())
+            }
+        }
+    }
+
+    /// Reads a document written in `format`. Structured formats follow
+    /// `docs/changelog.schema.json`.
+    pub fn load(input: &str, format: DocumentFormat) -> Result<Self, String> {
+        match format {
+            DocumentFormat::Markdown => Self::parse(input).map_err(|issue| issue.message()),
+            DocumentFormat::AsciiDoc | DocumentFormat::Rst => {
+                let markup = format.markup().expect("markup format");
+                Self::parse(&markup.to_markdown(input)).map_err(|issue| issue.message())
+            }
+            DocumentFormat::Json => serde_json::from_str(input).map_err(|e| e.to_string()),
+            DocumentFormat::Yaml => serde_yaml::from_str(input).map_err(|e| e.to_string()),
+            DocumentFormat::Toml => toml::from_str(input).map_err(|e| e.to_string()),
+            DocumentFormat::Html | DocumentFormat::Atom | DocumentFormat::Rss => {
+                Err("HTML and feed output cannot be read back as a changelog".to_string())
+            }
         }
     }
 
@@ -76,48 +247,57 @@ impl ChangelogDocument {
             });
         }
 
+        idx += 1;
+
+        // Reference-style link definitions at the very end form the footer;
+        // everything above it is parsed as preamble and releases.
+        let footer_start = find_footer_start(&lines, idx);
+        let links = lines[footer_start..]
+            .iter()
+            .filter_map(|line| parse_link_definition(line))
+            .collect();
+        let lines = &lines[..footer_start];
+        let fenced = fenced_lines(lines);
+        let is_heading = |idx: usize| !fenced[idx] && is_release_heading(lines[idx]);
+
+        let preamble_start = idx;
+        while idx < lines.len() && !is_heading(idx) {
+            idx += 1;
+        }
+
         let mut doc = ChangelogDocument {
             title: title_line.trim_start_matches("# ").to_string(),
+            preamble: join_block(&lines[preamble_start..idx]),
             releases: Vec::new(),
+            links,
+            compare: None,
         };
 
-        idx += 1;
-
         while idx < lines.len() {
             let line = lines[idx].trim();
-
-            if line.is_empty() {
-                idx += 1;
-                continue;
-            }
-
-            if !line.starts_with("## ") {
-                return Err(ParseIssue {
-                    line: idx + 1,
-                    expected: "a release heading '## [x.y.z] - YYYY-MM-DD'".to_string(),
-                    found: line.to_string(),
-                    fix: "add a release heading before sections and notes".to_string(),
-                });
-            }
-
             let (version, date, header) = parse_release_heading(line, idx + 1)?;
             idx += 1;
 
-            let mut sections: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            let mut description: Vec<&str> = Vec::new();
+            let mut sections: IndexMap<String, Vec<String>> = IndexMap::new();
+            let mut section_descriptions: BTreeMap<String, String> = BTreeMap::new();
             let mut current_section = String::new();
+            let mut current_note: Option<NoteBuffer> = None;
+            let mut section_prose: Vec<&str> = Vec::new();
 
-            while idx < lines.len() {
-                let current = lines[idx].trim();
-                if current.is_empty() {
-                    idx += 1;
-                    continue;
-                }
-
-                if current.starts_with("## ") {
-                    break;
-                }
+            while idx < lines.len() && !is_heading(idx) {
+                let raw = lines[idx];
+                let current = raw.trim();
+                // Lines in a code block are text, never headings or bullets.
+                let markup = !fenced[idx];
 
-                if current.starts_with("### ") {
+                if markup && current.starts_with("### ") {
+                    flush_note(&mut sections, &current_section, current_note.take());
+                    flush_prose(
+                        &mut section_descriptions,
+                        &current_section,
+                        &mut section_prose,
+                    );
                     current_section = current.trim_start_matches("### ").trim().to_string();
                     if current_section.is_empty() {
                         return Err(ParseIssue {
@@ -132,47 +312,81 @@ impl ChangelogDocument {
                     continue;
                 }
 
-                if !current.starts_with("- ") {
-                    return Err(ParseIssue {
-                        line: idx + 1,
-                        expected: "a bullet note '- ...' or a section heading '### ...'"
-                            .to_string(),
-                        found: current.to_string(),
-                        fix: "prefix notes with '- ' and group them under '### <Section>'"
-                            .to_string(),
-                    });
-                }
+                let indent = indent_width(raw);
+                let starts_note = markup
+                    && current.starts_with("- ")
+                    && current_note
+                        .as_ref()
+                        .is_none_or(|note| indent <= note.indent);
+                // Unindented text after a blank line closes the note above
+                // and starts prose under the section.
+                let ends_note = markup
+                    && !current.is_empty()
+                    && lines[idx - 1].trim().is_empty()
+                    && current_note
+                        .as_ref()
+                        .is_some_and(|note| indent <= note.indent);
 
                 if current_section.is_empty() {
-                    return Err(ParseIssue {
-                        line: idx + 1,
-                        expected: "a section heading before notes".to_string(),
-                        found: current.to_string(),
-                        fix: "insert a heading like '### Added' above this note".to_string(),
-                    });
+                    if markup && current.starts_with("- ") {
+                        return Err(ParseIssue {
+                            line: idx + 1,
+                            expected: "a section heading before notes".to_string(),
+                            found: current.to_string(),
+                            fix: "insert a heading like '### Added' above this note".to_string(),
+                        });
+                    }
+                    description.push(raw);
+                    idx += 1;
+                    continue;
                 }
 
-                let note = current.trim_start_matches("- ").trim().to_string();
-                if note.is_empty() {
-                    return Err(ParseIssue {
-                        line: idx + 1,
-                        expected: "a non-empty note".to_string(),
-                        found: current.to_string(),
-                        fix: "write text after '- '".to_string(),
+                if starts_note {
+                    flush_note(&mut sections, &current_section, current_note.take());
+                    let note = current.trim_start_matches("- ").trim();
+                    if note.is_empty() {
+                        return Err(ParseIssue {
+                            line: idx + 1,
+                            expected: "a non-empty note".to_string(),
+                            found: current.to_string(),
+                            fix: "write text after '- '".to_string(),
+                        });
+                    }
+                    current_note = Some(NoteBuffer {
+                        indent,
+                        lines: vec![note.to_string()],
                     });
+                } else if ends_note {
+                    flush_note(&mut sections, &current_section, current_note.take());
+                    if !section_prose.is_empty() {
+                        section_prose.push("");
+                    }
+                    section_prose.push(raw);
+                } else if let Some(note) = current_note.as_mut() {
+                    // Continuation lines and nested bullets belong to the
+                    // note above, re-indented relative to its bullet.
+                    let strip = indent.min(note.indent);
+                    note.lines.push(raw[strip..].trim_end().to_string());
+                } else {
+                    section_prose.push(raw);
                 }
-                sections
-                    .entry(current_section.clone())
-                    .or_default()
-                    .push(note);
                 idx += 1;
             }
 
+            flush_note(&mut sections, &current_section, current_note.take());
+            flush_prose(
+                &mut section_descriptions,
+                &current_section,
+                &mut section_prose,
+            );
+
             doc.releases.push(Release {
                 version,
                 date,
                 header,
+                description: join_block(&description),
                 sections,
+                section_descriptions,
             });
         }
 
@@ -189,7 +403,7 @@ impl ChangelogDocument {
             });
         }
 
-        let mut seen: BTreeSet<Version> = BTreeSet::new();
+        let mut seen: BTreeSet<ReleaseVersion> = BTreeSet::new();
         for (index, release) in self.releases.iter().enumerate() {
             if !seen.insert(release.version.clone()) {
                 return Err(ParseIssue {
@@ -200,7 +414,7 @@ impl ChangelogDocument {
                 });
             }
 
-            if release.sections.is_empty() && strict {
+            if release.sections.is_empty() && strict && !release.is_unreleased() {
                 return Err(ParseIssue {
                     line: index + 2,
                     expected: "at least one section per release in --strict mode".to_string(),
@@ -245,10 +459,51 @@ impl ChangelogDocument {
         self.releases.sort_by(|a, b| b.version.cmp(&a.version));
     }
 
-    pub fn remove_version(&mut self, version: &Version) -> bool {
+    pub fn unreleased(&self) -> Option<&Release> {
+        self.releases.iter().find(|r| r.is_unreleased())
+    }
+
+    /// Detaches the `Unreleased` entry so its notes can be folded into a
+    /// new release.
+    pub fn take_unreleased(&mut self) -> Option<Release> {
+        let idx = self.releases.iter().position(|r| r.is_unreleased())?;
+        Some(self.releases.remove(idx))
+    }
+
+    /// Detaches all pre-release entries of `version` (e.g. `2.0.0-rc.1`,
+    /// `2.0.0-rc.2` for `2.0.0`), oldest first.
+    pub fn take_prereleases(&mut self, version: &Version) -> Vec<Release> {
+        let (mut taken, kept): (Vec<Release>, Vec<Release>) =
+            self.releases.drain(..).partition(|release| {
+                release.version.semver().is_some_and(|v| {
+                    !v.pre.is_empty()
+                        && (v.major, v.minor, v.patch)
+                            == (version.major, version.minor, version.patch)
+                })
+            });
+        self.releases = kept;
+        taken.sort_by(|a, b| a.version.cmp(&b.version));
+        taken
+    }
+
+    pub fn latest_version(&self) -> Option<&Version> {
+        self.releases
+            .iter()
+            .filter_map(|r| r.version.semver())
+            .max()
+    }
+
+    pub fn remove_version(&mut self, version: &ReleaseVersion) -> bool {
         let previous = self.releases.len();
         self.releases.retain(|r| &r.version != version);
-        self.releases.len() != previous
+        if self.releases.len() == previous {
+            return false;
+        }
+
+        let label = version.to_string();
+        self.links
+            .retain(|link| !link.label.eq_ignore_ascii_case(&label));
+        true
     }
 
     pub fn upsert_release(
@@ -276,26 +531,76 @@ impl ChangelogDocument {
         Ok(())
     }
 
+    /// Link definitions for the footer. With `compare` set, every release
+    /// heading that renders a `[label]` gets a fresh compare link and stale
+    /// version links are dropped; other definitions are kept after them.
+    pub fn footer_links(&self) -> Vec<LinkDefinition> {
+        let Some(compare) = &self.compare else {
+            return self.links.clone();
+        };
+
+        let tag = |version: &Version| format!("{}{}", compare.tag_prefix, version);
+        let versions: Vec<&Version> = self
+            .releases
+            .iter()
+            .filter_map(|r| r.version.semver())
+            .collect();
+
+        let mut links = Vec::new();
+        for release in self.releases.iter().filter(|r| r.has_link_label()) {
+            let url = match &release.version {
+                ReleaseVersion::Unreleased => match versions.iter().max() {
+                    Some(latest) => compare.forge.compare_url(&tag(latest), "HEAD"),
+                    None => continue,
+                },
+                ReleaseVersion::Released(version) => {
+                    match versions.iter().filter(|other| **other < version).max() {
+                        Some(previous) => compare.forge.compare_url(&tag(previous), &tag(version)),
+                        None => compare.forge.tag_url(&tag(version)),
+                    }
+                }
+            };
+            links.push(LinkDefinition {
+                label: release.version.to_string(),
+                url,
+            });
+        }
+
+        links.extend(
+            self.links
+                .iter()
+                .filter(|link| {
+                    !link.label.eq_ignore_ascii_case("unreleased")
+                        && Version::parse(&link.label).is_err()
+                })
+                .cloned(),
+        );
+        links
+    }
+
     pub fn to_markdown(&self) -> String {
         let mut out = String::new();
         out.push_str("# ");
         out.push_str(&self.title);
         out.push_str("\n\n");
 
+        if !self.preamble.is_empty() {
+            out.push_str(&self.preamble);
+            out.push_str("\n\n");
+        }
+
         for release in &self.releases {
-            out.push_str(&render_release_header(release));
+            out.push_str(&release.heading());
             out.push_str("\n\n");
+            out.push_str(&release.body_markdown());
+        }
 
-            for (section, notes) in &release.sections {
-                out.push_str("### ");
-                out.push_str(section);
-                out.push('\n');
-                for note in notes {
-                    out.push_str("- ");
-                    out.push_str(note);
-                    out.push('\n');
-                }
-                out.push('\n');
+        let mut out = out.trim_end().to_string();
+        let links = self.footer_links();
+        if !links.is_empty() {
+            out.push_str("\n\n");
+            for link in &links {
+                out.push_str(&format!("[{}]: {}\n", link.label, link.url));
             }
         }
 
@@ -303,25 +608,319 @@ impl ChangelogDocument {
     }
 }
 
+impl ReleaseVersion {
+    /// Parses a version argument, accepting `unreleased` in any case.
+    pub fn parse(raw: &str) -> Result<Self, semver::Error> {
+        if raw.trim().eq_ignore_ascii_case("unreleased") {
+            return Ok(ReleaseVersion::Unreleased);
+        }
+        Version::parse(raw.trim()).map(ReleaseVersion::Released)
+    }
+
+    pub fn semver(&self) -> Option<&Version> {
+        match self {
+            ReleaseVersion::Released(version) => Some(version),
+            ReleaseVersion::Unreleased => None,
+        }
+    }
+}
+
+impl From<Version> for ReleaseVersion {
+    fn from(version: Version) -> Self {
+        ReleaseVersion::Released(version)
+    }
+}
+
+/// Serialized as the version string, or `Unreleased`.
+impl Serialize for ReleaseVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ReleaseVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        ReleaseVersion::parse(&raw).map_err(|e| {
+            serde::de::Error::custom(format!(
+                "invalid version '{}': expected SemVer or 'Unreleased' ({})",
+                raw, e
+            ))
+        })
+    }
+}
+
+impl fmt::Display for ReleaseVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReleaseVersion::Released(version) => write!(f, "{}", version),
+            ReleaseVersion::Unreleased => f.write_str("Unreleased"),
+        }
+    }
+}
+
 impl Release {
     pub fn new(version: Version) -> Self {
         Self {
-            version,
+            version: ReleaseVersion::Released(version),
             date: Some(Utc::now().format("%Y-%m-%d").to_string()),
             header: HeaderFormat::Default,
-            sections: BTreeMap::new(),
+            description: String::new(),
+            sections: IndexMap::new(),
+            section_descriptions: BTreeMap::new(),
         }
     }
 
+    pub fn unreleased() -> Self {
+        Self {
+            version: ReleaseVersion::Unreleased,
+            date: None,
+            header: HeaderFormat::Default,
+            description: String::new(),
+            sections: IndexMap::new(),
+            section_descriptions: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_unreleased(&self) -> bool {
+        self.version == ReleaseVersion::Unreleased
+    }
+
+    /// Whether the rendered heading shows the version as a `[label]` that a
+    /// footer link definition can resolve.
+    pub fn has_link_label(&self) -> bool {
+        match &self.header {
+            HeaderFormat::Default | HeaderFormat::VersionOnly => true,
+            HeaderFormat::Plain => false,
+            HeaderFormat::Custom(template) => {
+                self.is_unreleased() || template.contains("[{version}]")
+            }
+        }
+    }
+
+    /// The `## ...` heading `to_markdown` writes for this release.
+    pub fn heading(&self) -> String {
+        render_release_header(self)
+    }
+
+    /// How `other`, this release as read back from rendered output,
+    /// differs in version, date, description or notes; `None` when nothing
+    /// was lost.
+    pub fn content_difference(&self, other: &Release) -> Option<String> {
+        if self.version != other.version {
+            return Some(format!("version {} became {}", self.version, other.version));
+        }
+        if self.date != other.date {
+            return Some(format!(
+                "date {} became {}",
+                self.date.as_deref().unwrap_or("none"),
+                other.date.as_deref().unwrap_or("none")
+            ));
+        }
+        if self.description.trim() != other.description.trim() {
+            return Some("the description changed".to_string());
+        }
+
+        let names: BTreeSet<&String> = self.section_names().chain(other.section_names()).collect();
+        let difference = names.into_iter().find_map(|name| {
+            let notes = |release: &Release| release.sections.get(name).cloned().unwrap_or_default();
+            if notes(self) != notes(other) {
+                return Some(format!("the notes under {} changed", name));
+            }
+            (self.section_description(name).trim() != other.section_description(name).trim())
+                .then(|| format!("the text under {} changed", name))
+        });
+        difference.or_else(|| {
+            (!self.section_names().eq(other.section_names()))
+                .then(|| "the section order changed".to_string())
+        })
+    }
+
+    /// Names of sections with notes or a description, in document order.
+    pub fn section_names(&self) -> impl Iterator<Item = &String> {
+        let described = self
+            .section_descriptions
+            .keys()
+            .filter(|name| !self.sections.contains_key(*name));
+        self.sections.keys().chain(described)
+    }
+
+    pub fn section_description(&self, section: &str) -> &str {
+        self.section_descriptions
+            .get(section)
+            .map_or("", String::as_str)
+    }
+
     pub fn add_note(&mut self, section: String, note: String) {
         self.sections.entry(section).or_default().push(note);
     }
+
+    /// Drops repeated notes within each section, compared by
+    /// `canonical_note_key`, and sections left without notes.
+    pub fn dedupe_notes(&mut self) {
+        for notes in self.sections.values_mut() {
+            let mut seen = BTreeSet::new();
+            notes.retain(|note| seen.insert(canonical_note_key(note)));
+        }
+        self.sections.retain(|_, notes| !notes.is_empty());
+    }
+
+    /// Description and sections without the heading, e.g. for a forge
+    /// release page.
+    pub fn body_markdown(&self) -> String {
+        let mut out = String::new();
+        if !self.description.is_empty() {
+            out.push_str(&self.description);
+            out.push_str("\n\n");
+        }
+
+        for section in self.section_names() {
+            out.push_str("### ");
+            out.push_str(section);
+            out.push('\n');
+            let notes = self.sections.get(section).map_or(&[][..], Vec::as_slice);
+            let description = self.section_description(section);
+            if !description.is_empty() {
+                out.push_str(description);
+                out.push_str(if notes.is_empty() { "\n" } else { "\n\n" });
+            }
+            for note in notes {
+                out.push_str("- ");
+                out.push_str(note);
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Appends the descriptions and notes of `other` after this release's own.
+    pub fn absorb(&mut self, other: Release) {
+        if !other.description.is_empty() {
+            if !self.description.is_empty() {
+                self.description.push_str("\n\n");
+            }
+            self.description.push_str(&other.description);
+        }
+        for (section, notes) in other.sections {
+            for note in notes {
+                self.add_note(section.clone(), note);
+            }
+        }
+        for (section, description) in other.section_descriptions {
+            let own = self.section_descriptions.entry(section).or_default();
+            if !own.is_empty() {
+                own.push_str("\n\n");
+            }
+            own.push_str(&description);
+        }
+    }
+}
+
+struct NoteBuffer {
+    indent: usize,
+    lines: Vec<String>,
+}
+
+fn flush_note(
+    sections: &mut IndexMap<String, Vec<String>>,
+    section: &str,
+    note: Option<NoteBuffer>,
+) {
+    if let Some(note) = note {
+        let text = note.lines.join("\n").trim_end().to_string();
+        sections.entry(section.to_string()).or_default().push(text);
+    }
+}
+
+fn flush_prose(descriptions: &mut BTreeMap<String, String>, section: &str, prose: &mut Vec<&str>) {
+    let text = join_block(prose);
+    prose.clear();
+    if !text.is_empty() {
+        descriptions.insert(section.to_string(), text);
+    }
+}
+
+/// Marks the lines of fenced code blocks, fences included.
+fn fenced_lines(lines: &[&str]) -> Vec<bool> {
+    let mut open: Option<&str> = None;
+    lines
+        .iter()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let fence = ["```", "~~~"]
+                .into_iter()
+                .find(|fence| trimmed.starts_with(fence));
+            match (open, fence) {
+                (None, Some(fence)) => open = Some(fence),
+                (Some(current), Some(fence)) if current == fence => open = None,
+                (Some(_), _) => {}
+                (None, None) => return false,
+            }
+            true
+        })
+        .collect()
+}
+
+fn is_release_heading(line: &str) -> bool {
+    line.trim().starts_with("## ")
+}
+
+/// Byte length of the leading spaces and tabs. Other whitespace counts as
+/// text, so slicing a line at any indent width stays on a char boundary.
+pub fn indent_width(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+/// Joins raw lines into a block, dropping surrounding blank lines.
+fn join_block(lines: &[&str]) -> String {
+    let text = lines
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    text.trim_matches('\n').to_string()
+}
+
+fn find_footer_start(lines: &[&str], body_start: usize) -> usize {
+    let mut start = lines.len();
+    while start > body_start {
+        let line = lines[start - 1];
+        if line.trim().is_empty() || parse_link_definition(line).is_some() {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+
+    // Leading blank lines stay with the body; only definitions matter.
+    while start < lines.len() && lines[start].trim().is_empty() {
+        start += 1;
+    }
+    if start == lines.len() {
+        return lines.len();
+    }
+    start
+}
+
+pub fn parse_link_definition(line: &str) -> Option<LinkDefinition> {
+    let rest = line.trim().strip_prefix('[')?;
+    let (label, url) = rest.split_once("]:")?;
+    let url = url.trim();
+    if label.is_empty() || url.is_empty() || label.contains(']') {
+        return None;
+    }
+
+    Some(LinkDefinition {
+        label: label.to_string(),
+        url: url.to_string(),
+    })
 }
 
 fn parse_release_heading(
     line: &str,
     line_number: usize,
-) -> Result<(Version, Option<String>, HeaderFormat), ParseIssue> {
+) -> Result<(ReleaseVersion, Option<String>, HeaderFormat), ParseIssue> {
     let rest = line.trim_start_matches("## ").trim();
 
     let (version_raw, date_raw, header) = if rest.starts_with('[') {
@@ -332,7 +931,14 @@ fn parse_release_heading(
             fix: "use heading format like '## [1.2.3] - 2026-01-01'".to_string(),
         })?;
         let version = &rest[1..close];
-        let trailing = rest[close + 1..].trim();
+        let mut trailing = rest[close + 1..].trim();
+        // `## [1.2.0](https://...) - 2026-01-01`: the version may link
+        // straight to its compare view.
+        if let Some(target) = trailing.strip_prefix('(') {
+            if let Some(end) = target.find(')') {
+                trailing = target[end + 1..].trim();
+            }
+        }
         let date = trailing
             .strip_prefix('-')
             .map(|value| value.trim().to_string())
@@ -350,7 +956,7 @@ fn parse_release_heading(
             .next()
             .map(|d| d.trim().to_string())
             .filter(|v| !v.is_empty());
-        let header = if date.is_some() {
+        let header = if date.is_some() || version.eq_ignore_ascii_case("unreleased") {
             HeaderFormat::Plain
         } else {
             HeaderFormat::Custom("## {version}".to_string())
@@ -358,13 +964,20 @@ fn parse_release_heading(
         (version, date, header)
     };
 
-    let version = Version::parse(&version_raw).map_err(|_| ParseIssue {
+    let version = ReleaseVersion::parse(&version_raw).map_err(|_| ParseIssue {
         line: line_number,
-        expected: "a semantic version (x.y.z)".to_string(),
+        expected: "a semantic version (x.y.z) or 'Unreleased'".to_string(),
         found: version_raw,
         fix: "replace with a valid version like 1.4.2".to_string(),
     })?;
 
+    // Unreleased entries never carry a date; keep the heading style only.
+    let (date_raw, header) = match (&version, header) {
+        (ReleaseVersion::Unreleased, HeaderFormat::VersionOnly) => (None, HeaderFormat::Default),
+        (ReleaseVersion::Unreleased, header) => (None, header),
+        (_, header) => (date_raw, header),
+    };
+
     Ok((version, date_raw, header))
 }
 
@@ -385,6 +998,13 @@ pub fn parse_header_format(input: &str) -> HeaderFormat {
 
 fn render_release_header(release: &Release) -> String {
     let version = release.version.to_string();
+    if release.is_unreleased() {
+        return match &release.header {
+            HeaderFormat::Plain => format!("## {}", version),
+            _ => format!("## [{}]", version),
+        };
+    }
+
     let date = release.date.clone().unwrap_or_default();
     match &release.header {
         HeaderFormat::Default => {
@@ -423,6 +1043,81 @@ mod tests {
         assert_eq!(doc, reparsed);
     }
 
+    #[test]
+    fn keeps_continuations_indented_with_unicode_whitespace() {
+        let doc = ChangelogDocument::parse(
+            "# Changelog\n\n## [1.0.0] - 2026-01-01\n\n### Added\n - note\n\u{3000}continued\n",
+        )
+        .expect("parse changelog");
+        assert_eq!(
+            doc.releases[0].sections["Added"],
+            ["note\n\u{3000}continued"]
+        );
+    }
+
+    #[test]
+    fn reports_what_a_rendered_release_lost() {
+        let doc = ChangelogDocument::parse(
+            "# Changelog\n\n## [1.0.0] - 2026-01-01\n\nFirst release.\n\n### Added\n- export\n- import\n",
+        )
+        .expect("valid changelog");
+        let release = &doc.releases[0];
+        assert_eq!(release.content_difference(release), None);
+
+        let mut lossy = release.clone();
+        lossy.sections.get_mut("Added").expect("section").pop();
+        assert_eq!(
+            release.content_difference(&lossy).as_deref(),
+            Some("the notes under Added changed")
+        );
+
+        let undated = Release {
+            date: None,
+            ..release.clone()
+        };
+        assert_eq!(
+            release.content_difference(&undated).as_deref(),
+            Some("date 2026-01-01 became none")
+        );
+
+        let bare = Release {
+            description: String::new(),
+            ..release.clone()
+        };
+        assert_eq!(
+            release.content_difference(&bare).as_deref(),
+            Some("the description changed")
+        );
+
+        let mut described = release.clone();
+        described
+            .section_descriptions
+            .insert("Added".to_string(), "Highlights first.".to_string());
+        assert_eq!(
+            release.content_difference(&described).as_deref(),
+            Some("the text under Added changed")
+        );
+
+        let mut extended = release.clone();
+        extended.add_note("Security".to_string(), "patch leak".to_string());
+        let mut reordered = extended.clone();
+        reordered.sections.reverse();
+        assert_eq!(
+            extended.content_difference(&reordered).as_deref(),
+            Some("the section order changed")
+        );
+    }
+
+    #[test]
+    fn parses_release_headings_linking_to_compare_view() {
+        let doc = ChangelogDocument::parse(
+            "# Changelog\n\n## [1.1.0](https://example.com/compare/v1.0.0...v1.1.0) - 2026-01-01\n\n### Added\n- a\n",
+        )
+        .expect("parse changelog");
+        assert_eq!(doc.releases[0].version.to_string(), "1.1.0");
+        assert_eq!(doc.releases[0].date.as_deref(), Some("2026-01-01"));
+    }
+
     #[test]
     fn reports_parse_location() {
         let input = "# Changelog\n\n## [1.0.0]\n\n- orphan note\n";
@@ -440,4 +1135,97 @@ mod tests {
         let err = doc.validate(false).expect_err("should fail ordering");
         assert!(err.message().contains("SemVer"));
     }
+
+    #[test]
+    fn unreleased_section_roundtrips_and_sorts_first() {
+        let input = "# Changelog\n\n## [Unreleased]\n\n### Added\n- pending\n\n## [1.0.0] - 2026-01-01\n\n### Fixed\n- b\n";
+
+        let doc = ChangelogDocument::parse(input).expect("parse changelog");
+        doc.validate(true).expect("valid changelog");
+        assert!(doc.releases[0].is_unreleased());
+        assert_eq!(doc.to_markdown(), input);
+
+        let mut doc = doc;
+        doc.upsert_release(Release::new(Version::new(1, 1, 0)), false)
+            .expect("insert release");
+        assert!(doc.releases[0].is_unreleased());
+        assert_eq!(doc.latest_version(), Some(&Version::new(1, 1, 0)));
+    }
+
+    #[test]
+    fn regenerates_compare_link_footer() {
+        let input = "# Changelog\n\n## [Unreleased]\n\n## [1.1.0] - 2026-02-01\n\n### Added\n- a\n\n## [1.0.0] - 2026-01-01\n\n### Added\n- b\n\n[1.0.0]: https://stale.example\n[docs]: https://docs.example\n";
+
+        let mut doc = ChangelogDocument::parse(input).expect("parse changelog");
+        doc.compare = Some(CompareLinks {
+            forge: Forge::from_remote("git@github.com:o/r.git", None).expect("forge"),
+            tag_prefix: "v".to_string(),
+        });
+
+        let rendered = doc.to_markdown();
+        assert!(rendered.ends_with(
+            "[Unreleased]: https://github.com/o/r/compare/v1.1.0...HEAD\n\
+             [1.1.0]: https://github.com/o/r/compare/v1.0.0...v1.1.0\n\
+             [1.0.0]: https://github.com/o/r/releases/tag/v1.0.0\n\
+             [docs]: https://docs.example\n"
+        ));
+    }
+
+    #[test]
+    fn serializes_to_the_published_schema() {
+        let doc = ChangelogDocument::parse(
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n- wip\n\n## [1.1.0] - 2026-01-01\n\nProse.\n\n### Fixed\n- b\n  - nested\n\n[1.1.0]: https://example.com/v1.1.0\n",
+        )
+        .expect("parse changelog");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&doc.render(DocumentFormat::Json).expect("render json"))
+                .expect("valid json");
+        assert_eq!(json["releases"][0]["version"], "Unreleased");
+        assert_eq!(json["releases"][1]["date"], "2026-01-01");
+        assert_eq!(json["releases"][1]["sections"]["Fixed"][0], "b\n  - nested");
+        assert!(json.get("preamble").is_none());
+
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("../../docs/changelog.schema.json"))
+                .expect("valid schema");
+        let keys = |value: &serde_json::Value| -> BTreeSet<String> {
+            value.as_object().expect("object").keys().cloned().collect()
+        };
+        let allowed = |path: &str| keys(schema.pointer(path).expect("schema path"));
+        assert!(keys(&json).is_subset(&allowed("/properties")));
+        assert!(keys(&json["releases"][1]).is_subset(&allowed("/$defs/release/properties")));
+        assert!(keys(&json["links"][0]).is_subset(&allowed("/$defs/link/properties")));
+
+        let yaml = doc.render(DocumentFormat::Yaml).expect("render yaml");
+        let mut reparsed: ChangelogDocument = serde_yaml::from_str(&yaml).expect("parse yaml");
+        reparsed.compare = doc.compare.clone();
+        assert_eq!(reparsed, doc);
+    }
+
+    #[test]
+    fn loads_structured_formats_by_extension() {
+        let format = DocumentFormat::from_path(Path::new("release/changelog.TOML"));
+        assert_eq!(format, Some(DocumentFormat::Toml));
+        assert_eq!(DocumentFormat::from_path(Path::new("notes.txt")), None);
+
+        let doc = ChangelogDocument::load(
+            "title = \"Changelog\"\n\n[[releases]]\nversion = \"1.0.0\"\n\n[releases.sections]\nAdded = [\"a\"]\n",
+            DocumentFormat::Toml,
+        )
+        .expect("load toml");
+        assert_eq!(doc.releases[0].date, None);
+        assert_eq!(doc.releases[0].header, HeaderFormat::Default);
+        assert_eq!(
+            doc.to_markdown(),
+            "# Changelog\n\n## [1.0.0]\n\n### Added\n- a\n"
+        );
+
+        let err = ChangelogDocument::load(
+            "{\"title\": \"x\", \"releases\": [{\"version\": \"one\"}]}",
+            DocumentFormat::Json,
+        )
+        .expect_err("invalid version");
+        assert!(err.contains("invalid version 'one'"), "{}", err);
+    }
 }
@@ -0,0 +1,188 @@
+//! Configurable `[[commit_parsers]]` rules for commit messages that are
+//! not conventional commits, e.g. Gitmoji or `[TICKET-123] Fix x`.
+
+use regex::{Captures, Regex};
+use serde::Deserialize;
+
+/// One rule as written in the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ParserRule {
+    /// Regex matched against the summary line.
+    pub pattern: String,
+    /// Section for matching commits. Without it, a `type` capture is mapped
+    /// like a conventional commit type.
+    pub section: Option<String>,
+    /// Leave matching commits out of the changelog.
+    #[serde(default)]
+    pub skip: bool,
+    /// Capture group (name or number) holding the description. Defaults to
+    /// a `description` group, else the summary without the matched text.
+    pub description: Option<String>,
+    /// Capture group holding the scope; defaults to a `scope` group.
+    pub scope: Option<String>,
+    /// Note text built from the captures, e.g. `"$description ($ticket)"`.
+    pub replace: Option<String>,
+}
+
+/// Rules compiled in config order; the first matching rule decides.
+#[derive(Debug, Clone, Default)]
+pub struct CommitParsers {
+    rules: Vec<(ParserRule, Regex)>,
+}
+
+/// How a rule classified a summary line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedMessage {
+    /// 1-based position of the rule in the config.
+    pub rule: usize,
+    pub pattern: String,
+    pub skip: bool,
+    pub section: Option<String>,
+    /// The `type` capture, for mapping to a section.
+    pub kind: Option<String>,
+    pub scope: Option<String>,
+    pub description: String,
+    /// Set by a non-empty `breaking` capture, e.g. `(?P<breaking>!)`.
+    pub breaking: bool,
+}
+
+impl CommitParsers {
+    pub fn new(rules: &[ParserRule]) -> Result<Self, String> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                Regex::new(&rule.pattern)
+                    .map(|regex| (rule.clone(), regex))
+                    .map_err(|e| format!("commit parser {}: invalid pattern: {}", index + 1, e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Classifies `summary` with the first matching rule; `None` leaves it
+    /// to the conventional commit parser.
+    pub fn parse(&self, summary: &str) -> Option<ParsedMessage> {
+        self.rules
+            .iter()
+            .enumerate()
+            .find_map(|(index, (rule, regex))| {
+                let captures = regex.captures(summary)?;
+                Some(ParsedMessage {
+                    rule: index + 1,
+                    pattern: rule.pattern.clone(),
+                    skip: rule.skip,
+                    section: rule.section.clone(),
+                    kind: group(&captures, "type"),
+                    scope: group(&captures, rule.scope.as_deref().unwrap_or("scope")),
+                    description: description(rule, regex, &captures, summary),
+                    breaking: group(&captures, "breaking").is_some(),
+                })
+            })
+    }
+}
+
+fn description(rule: &ParserRule, regex: &Regex, captures: &Captures, summary: &str) -> String {
+    if let Some(template) = &rule.replace {
+        let mut text = String::new();
+        captures.expand(template, &mut text);
+        return text.trim().to_string();
+    }
+    if let Some(text) = group(
+        captures,
+        rule.description.as_deref().unwrap_or("description"),
+    ) {
+        return text;
+    }
+
+    let stripped = regex.replace(summary, "");
+    match stripped.trim() {
+        "" => summary.trim().to_string(),
+        text => text.to_string(),
+    }
+}
+
+/// Trimmed text of a non-empty capture group given by name or number.
+fn group(captures: &Captures, name: &str) -> Option<String> {
+    let found = match name.parse::<usize>() {
+        Ok(index) => captures.get(index),
+        Err(_) => captures.name(name),
+    };
+    found
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str) -> ParserRule {
+        ParserRule {
+            pattern: pattern.to_string(),
+            ..ParserRule::default()
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let parsers = CommitParsers::new(&[
+            ParserRule {
+                section: Some("Added".to_string()),
+                ..rule(r"^:sparkles:")
+            },
+            ParserRule {
+                skip: true,
+                ..rule(r"^Merge branch")
+            },
+            ParserRule {
+                section: Some("Fixed".to_string()),
+                scope: Some("1".to_string()),
+                ..rule(r"^\[([A-Z]+-\d+)\]")
+            },
+            rule(r"^(?P<type>\w+)(?P<breaking>!)?: (?P<description>.+)$"),
+        ])
+        .expect("valid rules");
+
+        let gitmoji = parsers.parse(":sparkles: add export").expect("gitmoji");
+        assert_eq!(gitmoji.rule, 1);
+        assert_eq!(gitmoji.section.as_deref(), Some("Added"));
+        assert_eq!(gitmoji.description, "add export");
+
+        assert!(parsers.parse("Merge branch 'main'").expect("merge").skip);
+
+        let ticket = parsers.parse("[WEB-12] Fix login").expect("ticket");
+        assert_eq!(ticket.scope.as_deref(), Some("WEB-12"));
+        assert_eq!(ticket.description, "Fix login");
+
+        let angular = parsers.parse("perf!: faster io").expect("angular");
+        assert_eq!(angular.kind.as_deref(), Some("perf"));
+        assert!(angular.breaking);
+        assert_eq!(angular.description, "faster io");
+
+        assert!(parsers.parse("Update README").is_none());
+    }
+
+    #[test]
+    fn replace_expands_captures() {
+        let parsers = CommitParsers::new(&[ParserRule {
+            replace: Some("$description ($ticket)".to_string()),
+            ..rule(r"^\[(?P<ticket>[A-Z]+-\d+)\] (?P<description>.+)")
+        }])
+        .expect("valid rules");
+        assert_eq!(
+            parsers
+                .parse("[WEB-12] Fix login")
+                .expect("match")
+                .description,
+            "Fix login (WEB-12)"
+        );
+
+        let error = CommitParsers::new(&[rule("(")]).expect_err("invalid regex");
+        assert!(error.starts_with("commit parser 1: invalid pattern"));
+    }
+}
@@ -0,0 +1,347 @@
+//! Data passed to Handlebars templates: [`TemplateContext`] for the notes
+//! of `generate --template`, [`DocumentContext`] for a whole changelog.
+
+use crate::core::changelog::{ChangelogDocument, LinkDefinition, Release};
+use crate::core::forge::Forge;
+use crate::core::notes::{canonical_note_key, should_ignore_commit, Note, PullRequestLink};
+use crate::domain::commit::Commit;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+const SHORT_HASH_LEN: usize = 7;
+
+/// Root object of a template. Fields are set by the caller after
+/// [`TemplateContext::new`] fills in the notes, commits and contributors.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateContext {
+    /// Version the notes describe, or `Unreleased`.
+    pub version: String,
+    pub previous_version: Option<String>,
+    /// Generation date, `YYYY-MM-DD`.
+    pub date: String,
+    pub compare_url: Option<String>,
+    pub repository: Option<TemplateRepository>,
+    pub contributors: Vec<Contributor>,
+    pub sections: Vec<TemplateSection>,
+    pub commits: Vec<TemplateCommit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateRepository {
+    /// Web location, e.g. `https://github.com/acme/widgets`.
+    pub url: String,
+    /// `github`, `gitlab`, `gitea` or `bitbucket`.
+    pub kind: String,
+    /// Path on the forge, e.g. `acme/widgets`.
+    pub path: String,
+}
+
+/// A commit author, most active first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Contributor {
+    pub name: String,
+    pub email: Option<String>,
+    pub commits: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateSection {
+    pub name: String,
+    pub notes: Vec<TemplateNote>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateNote {
+    /// Description without scope or pull request link.
+    pub text: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub hash: Option<String>,
+    pub short_hash: Option<String>,
+    pub author: Option<String>,
+    pub pull_request: Option<PullRequestLink>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateCommit {
+    pub hash: String,
+    pub short_hash: String,
+    /// Summary line.
+    pub message: String,
+    pub body: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub author: String,
+    pub email: String,
+    /// Author date, `YYYY-MM-DD`.
+    pub date: Option<String>,
+    pub pull_request: Option<u64>,
+}
+
+impl TemplateContext {
+    /// Context for `notes` written from `commits`: sections in name order
+    /// with duplicate notes dropped, and commits that carry an ignore
+    /// marker left out.
+    pub fn new(notes: &[Note], commits: &[Commit]) -> Self {
+        let commits: Vec<&Commit> = commits
+            .iter()
+            .filter(|commit| !should_ignore_commit(&commit.message))
+            .collect();
+
+        Self {
+            version: "Unreleased".to_string(),
+            previous_version: None,
+            date: Utc::now().format("%Y-%m-%d").to_string(),
+            compare_url: None,
+            repository: None,
+            contributors: contributors(&commits),
+            sections: sections(notes),
+            commits: commits.into_iter().map(TemplateCommit::from).collect(),
+        }
+    }
+}
+
+/// Root object of a document template, as rendered by `release` and `show`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentContext {
+    pub title: String,
+    pub preamble: String,
+    pub releases: Vec<ReleaseContext>,
+    /// Footer link definitions, including regenerated compare links.
+    pub links: Vec<LinkDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseContext {
+    pub version: String,
+    pub unreleased: bool,
+    pub date: Option<String>,
+    /// The heading the built-in layout writes, e.g. `## [1.2.0] - 2026-01-01`.
+    pub heading: String,
+    /// Next lower version in the document.
+    pub previous_version: Option<String>,
+    /// Footer link for this version, usually its compare view.
+    pub compare_url: Option<String>,
+    pub description: String,
+    pub sections: Vec<ReleaseSection>,
+}
+
+/// Notes are Markdown; continuation lines keep their indentation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseSection {
+    pub name: String,
+    /// Prose under the heading, outside the notes.
+    pub description: String,
+    pub notes: Vec<String>,
+}
+
+impl DocumentContext {
+    pub fn new(doc: &ChangelogDocument) -> Self {
+        let links = doc.footer_links();
+        let releases = doc
+            .releases
+            .iter()
+            .map(|release| {
+                let version = release.version.to_string();
+                ReleaseContext {
+                    unreleased: release.is_unreleased(),
+                    date: release.date.clone(),
+                    heading: release.heading(),
+                    previous_version: previous_version(doc, release),
+                    compare_url: links
+                        .iter()
+                        .find(|link| link.label.eq_ignore_ascii_case(&version))
+                        .map(|link| link.url.clone()),
+                    description: release.description.clone(),
+                    sections: release
+                        .section_names()
+                        .map(|name| ReleaseSection {
+                            name: name.clone(),
+                            description: release.section_description(name).to_string(),
+                            notes: release.sections.get(name).cloned().unwrap_or_default(),
+                        })
+                        .collect(),
+                    version,
+                }
+            })
+            .collect();
+
+        Self {
+            title: doc.title.clone(),
+            preamble: doc.preamble.clone(),
+            releases,
+            links,
+        }
+    }
+}
+
+fn previous_version(doc: &ChangelogDocument, release: &Release) -> Option<String> {
+    doc.releases
+        .iter()
+        .filter(|other| other.version < release.version && !other.is_unreleased())
+        .map(|other| &other.version)
+        .max()
+        .map(ToString::to_string)
+}
+
+impl From<&Forge> for TemplateRepository {
+    fn from(forge: &Forge) -> Self {
+        Self {
+            url: forge.base_url.clone(),
+            kind: forge.kind.to_string().to_lowercase(),
+            path: forge.project_path().to_string(),
+        }
+    }
+}
+
+impl From<&Note> for TemplateNote {
+    fn from(note: &Note) -> Self {
+        Self {
+            text: note.text.clone(),
+            scope: note.scope.clone(),
+            breaking: note.breaking,
+            hash: note.hash.clone(),
+            short_hash: note.hash.as_deref().map(short_hash),
+            author: note.author.clone(),
+            pull_request: note.pull_request.clone(),
+        }
+    }
+}
+
+impl From<&Commit> for TemplateCommit {
+    fn from(commit: &Commit) -> Self {
+        Self {
+            hash: commit.hash.clone(),
+            short_hash: short_hash(&commit.hash),
+            message: commit.message.clone(),
+            body: commit.body.clone(),
+            scope: commit.scope.clone(),
+            breaking: commit.breaking,
+            author: commit.author_name.clone(),
+            email: commit.author_email.clone(),
+            date: (commit.authored_at != 0)
+                .then(|| DateTime::from_timestamp(commit.authored_at, 0))
+                .flatten()
+                .map(|date| date.format("%Y-%m-%d").to_string()),
+            pull_request: commit.pull_request_number(),
+        }
+    }
+}
+
+/// First seven characters of a commit hash.
+pub fn short_hash(hash: &str) -> String {
+    hash.chars().take(SHORT_HASH_LEN).collect()
+}
+
+fn sections(notes: &[Note]) -> Vec<TemplateSection> {
+    let mut grouped: BTreeMap<&str, Vec<TemplateNote>> = BTreeMap::new();
+    let mut seen: BTreeSet<(&str, String)> = BTreeSet::new();
+    for note in notes {
+        let key = canonical_note_key(&format!(
+            "{} {}",
+            note.scope.as_deref().unwrap_or_default(),
+            note.linked_text()
+        ));
+        if seen.insert((&note.section, key)) {
+            grouped
+                .entry(&note.section)
+                .or_default()
+                .push(TemplateNote::from(note));
+        }
+    }
+
+    grouped
+        .into_iter()
+        .map(|(name, notes)| TemplateSection {
+            name: name.to_string(),
+            notes,
+        })
+        .collect()
+}
+
+/// Authors keyed by email (or name when it is missing), ordered by commit
+/// count and then name.
+fn contributors(commits: &[&Commit]) -> Vec<Contributor> {
+    let mut by_author: BTreeMap<String, Contributor> = BTreeMap::new();
+    for commit in commits {
+        if commit.author_name.is_empty() && commit.author_email.is_empty() {
+            continue;
+        }
+        let key = match commit.author_email.is_empty() {
+            true => commit.author_name.clone(),
+            false => commit.author_email.to_lowercase(),
+        };
+        by_author
+            .entry(key)
+            .or_insert_with(|| Contributor {
+                name: commit.author_name.clone(),
+                email: (!commit.author_email.is_empty()).then(|| commit.author_email.clone()),
+                commits: 0,
+            })
+            .commits += 1;
+    }
+
+    let mut contributors: Vec<Contributor> = by_author.into_values().collect();
+    contributors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    contributors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::commit_parser::CommitParsers;
+    use crate::core::notes::collect_notes;
+
+    fn commit(hash: &str, message: &str, author: &str) -> Commit {
+        Commit {
+            author_name: author.to_string(),
+            author_email: format!("{}@example.com", author.to_lowercase()),
+            authored_at: 1_767_225_600,
+            ..Commit::new(hash, message)
+        }
+    }
+
+    #[test]
+    fn builds_notes_commits_and_contributors() {
+        let commits = vec![
+            commit("0123456789abcdef", "feat(api)!: drop v1 (#12)", "Alice"),
+            commit("fedcba9876543210", "fix: crash", "Bob"),
+            commit("aaaaaaaaaaaaaaaa", "fix: crash", "Alice"),
+            commit("bbbbbbbbbbbbbbbb", "chore: tidy (skip changelog)", "Carol"),
+        ];
+        let notes = collect_notes(&commits, &BTreeMap::new(), &CommitParsers::default());
+        let context = TemplateContext::new(&notes, &commits);
+
+        let names: Vec<&str> = context.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Added", "Breaking Changes", "Fixed"]);
+        assert_eq!(context.sections[2].notes.len(), 1);
+
+        let added = &context.sections[0].notes[0];
+        assert_eq!(added.text, "drop v1 (#12)");
+        assert_eq!(added.scope.as_deref(), Some("api"));
+        assert!(added.breaking);
+        assert_eq!(added.short_hash.as_deref(), Some("0123456"));
+        assert_eq!(added.author.as_deref(), Some("Alice"));
+
+        assert_eq!(context.commits.len(), 3);
+        assert_eq!(context.commits[0].pull_request, Some(12));
+        assert_eq!(context.commits[0].date.as_deref(), Some("2026-01-01"));
+
+        assert_eq!(
+            context.contributors,
+            vec![
+                Contributor {
+                    name: "Alice".to_string(),
+                    email: Some("alice@example.com".to_string()),
+                    commits: 2,
+                },
+                Contributor {
+                    name: "Bob".to_string(),
+                    email: Some("bob@example.com".to_string()),
+                    commits: 1,
+                },
+            ]
+        );
+    }
+}
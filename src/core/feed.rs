@@ -0,0 +1,180 @@
+//! Atom and RSS feeds with one entry per release.
+
+use crate::core::changelog::{ChangelogDocument, Release};
+use crate::core::html::{anchor, escape, release_html};
+use chrono::{NaiveDate, Utc};
+
+const GENERATOR: &str = "changeloggen-cli";
+
+/// An Atom 1.0 feed. `site_url` is the page the changelog is published at;
+/// entries link to its per-version anchors. Without it, ids are URNs.
+pub fn atom(doc: &ChangelogDocument, site_url: Option<&str>) -> String {
+    let entries = feed_releases(doc);
+    let updated = entries
+        .iter()
+        .filter_map(|release| release_date(release))
+        .max()
+        .map(|date| atom_date(&date))
+        .unwrap_or_else(|| Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string());
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    out.push_str(&format!("  <title>{}</title>\n", escape(&doc.title)));
+    out.push_str(&format!("  <id>{}</id>\n", escape(&feed_id(doc, site_url))));
+    if let Some(url) = site_url {
+        out.push_str(&format!("  <link href=\"{}\"/>\n", escape(url)));
+    }
+    out.push_str(&format!("  <updated>{}</updated>\n", updated));
+    out.push_str(&format!(
+        "  <author><name>{}</name></author>\n",
+        escape(&doc.title)
+    ));
+    out.push_str(&format!("  <generator>{}</generator>\n", GENERATOR));
+
+    for release in entries {
+        let entry_updated = release_date(release)
+            .map(|date| atom_date(&date))
+            .unwrap_or_else(|| updated.clone());
+        out.push_str("  <entry>\n");
+        out.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape(&release.version.to_string())
+        ));
+        out.push_str(&format!(
+            "    <id>{}</id>\n",
+            escape(&entry_id(doc, release, site_url))
+        ));
+        if let Some(url) = entry_url(release, site_url) {
+            out.push_str(&format!("    <link href=\"{}\"/>\n", escape(&url)));
+        }
+        out.push_str(&format!("    <updated>{}</updated>\n", entry_updated));
+        out.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape(&release_html(doc, release))
+        ));
+        out.push_str("  </entry>\n");
+    }
+
+    out.push_str("</feed>\n");
+    out
+}
+
+/// An RSS 2.0 feed with the same entries as [`atom`]. RSS requires a
+/// channel link, so unlike Atom the `site_url` is mandatory.
+pub fn rss(doc: &ChangelogDocument, site_url: &str) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<rss version=\"2.0\">\n<channel>\n");
+    out.push_str(&format!("  <title>{}</title>\n", escape(&doc.title)));
+    out.push_str(&format!("  <link>{}</link>\n", escape(site_url)));
+    out.push_str(&format!(
+        "  <description>Releases of {}</description>\n",
+        escape(&doc.title)
+    ));
+    out.push_str(&format!("  <generator>{}</generator>\n", GENERATOR));
+
+    for release in feed_releases(doc) {
+        let url = entry_id(doc, release, Some(site_url));
+        out.push_str("  <item>\n");
+        out.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape(&release.version.to_string())
+        ));
+        out.push_str(&format!("    <link>{}</link>\n", escape(&url)));
+        out.push_str(&format!(
+            "    <guid isPermaLink=\"true\">{}</guid>\n",
+            escape(&url)
+        ));
+        if let Some(date) = release_date(release) {
+            out.push_str(&format!(
+                "    <pubDate>{}</pubDate>\n",
+                date.format("%a, %d %b %Y 00:00:00 +0000")
+            ));
+        }
+        out.push_str(&format!(
+            "    <description>{}</description>\n",
+            escape(&release_html(doc, release))
+        ));
+        out.push_str("  </item>\n");
+    }
+
+    out.push_str("</channel>\n</rss>\n");
+    out
+}
+
+/// Released versions; notes under `Unreleased` are not announced.
+fn feed_releases(doc: &ChangelogDocument) -> Vec<&Release> {
+    doc.releases
+        .iter()
+        .filter(|release| !release.is_unreleased())
+        .collect()
+}
+
+fn release_date(release: &Release) -> Option<NaiveDate> {
+    let date = release.date.as_deref()?;
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+}
+
+fn atom_date(date: &NaiveDate) -> String {
+    date.format("%Y-%m-%dT00:00:00Z").to_string()
+}
+
+fn feed_id(doc: &ChangelogDocument, site_url: Option<&str>) -> String {
+    match site_url {
+        Some(url) => url.to_string(),
+        None => format!("urn:changeloggen:{}", slug(&doc.title)),
+    }
+}
+
+fn entry_url(release: &Release, site_url: Option<&str>) -> Option<String> {
+    site_url.map(|url| format!("{}#{}", url.trim_end_matches('#'), anchor(release)))
+}
+
+fn entry_id(doc: &ChangelogDocument, release: &Release, site_url: Option<&str>) -> String {
+    entry_url(release, site_url)
+        .unwrap_or_else(|| format!("{}:{}", feed_id(doc, None), release.version))
+}
+
+fn slug(text: &str) -> String {
+    let slug: Vec<String> = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_ascii_lowercase())
+        .collect();
+    slug.join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> ChangelogDocument {
+        ChangelogDocument::parse(
+            "# Acme Widgets\n\n## [Unreleased]\n\n### Added\n- wip\n\n## [1.1.0] - 2026-01-02\n\n### Added\n- export & import\n\n## [1.0.0] - 2025-12-01\n\n### Fixed\n- crash\n",
+        )
+        .expect("parse changelog")
+    }
+
+    #[test]
+    fn renders_atom_entries_per_release() {
+        let feed = atom(&document(), Some("https://acme.dev/changelog/"));
+        assert!(feed.contains("<id>https://acme.dev/changelog/</id>"));
+        assert!(feed.contains("<updated>2026-01-02T00:00:00Z</updated>\n  <author>"));
+        assert!(feed.contains(
+            "<title>1.1.0</title>\n    <id>https://acme.dev/changelog/#1.1.0</id>\n    <link href=\"https://acme.dev/changelog/#1.1.0\"/>\n    <updated>2026-01-02T00:00:00Z</updated>"
+        ));
+        assert!(feed.contains("&lt;li&gt;export &amp;amp; import&lt;/li&gt;"));
+        assert!(!feed.contains("Unreleased"));
+        assert_eq!(feed.matches("<entry>").count(), 2);
+    }
+
+    #[test]
+    fn renders_rss_items_per_release() {
+        let feed = rss(&document(), "https://acme.dev/changelog/");
+        assert!(feed
+            .contains("<title>Acme Widgets</title>\n  <link>https://acme.dev/changelog/</link>"));
+        assert!(feed.contains(
+            "<link>https://acme.dev/changelog/#1.0.0</link>\n    <guid isPermaLink=\"true\">https://acme.dev/changelog/#1.0.0</guid>\n    <pubDate>Mon, 01 Dec 2025 00:00:00 +0000</pubDate>"
+        ));
+        assert_eq!(feed.matches("<item>").count(), 2);
+    }
+}
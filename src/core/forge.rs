@@ -0,0 +1,197 @@
+use std::fmt;
+
+/// Hosting services whose compare/tag URL shapes we know how to build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    Gitea,
+    Bitbucket,
+}
+
+/// Web location of a repository on a forge, e.g. `https://github.com/o/r`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forge {
+    pub kind: ForgeKind,
+    pub base_url: String,
+}
+
+/// A release page on a forge, created or updated for an existing tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForgeRelease {
+    pub tag: String,
+    pub name: String,
+    pub body: String,
+    pub draft: bool,
+    pub prerelease: bool,
+}
+
+impl ForgeKind {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "github" => Some(ForgeKind::GitHub),
+            "gitlab" => Some(ForgeKind::GitLab),
+            "gitea" | "forgejo" | "codeberg" => Some(ForgeKind::Gitea),
+            "bitbucket" => Some(ForgeKind::Bitbucket),
+            _ => None,
+        }
+    }
+
+    fn detect(host: &str) -> Option<Self> {
+        let host = host.to_lowercase();
+        if host.contains("github") {
+            Some(ForgeKind::GitHub)
+        } else if host.contains("gitlab") {
+            Some(ForgeKind::GitLab)
+        } else if host.contains("bitbucket") {
+            Some(ForgeKind::Bitbucket)
+        } else if host.contains("gitea") || host.contains("codeberg") || host.contains("forgejo") {
+            Some(ForgeKind::Gitea)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ForgeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ForgeKind::GitHub => "GitHub",
+            ForgeKind::GitLab => "GitLab",
+            ForgeKind::Gitea => "Gitea",
+            ForgeKind::Bitbucket => "Bitbucket",
+        })
+    }
+}
+
+impl Forge {
+    /// Builds a forge from a git remote URL (`https://`, `ssh://` or
+    /// scp-like `git@host:owner/repo.git`). The kind is detected from the
+    /// host name unless given explicitly.
+    pub fn from_remote(remote: &str, kind: Option<ForgeKind>) -> Option<Self> {
+        let (host, path) = split_remote(remote.trim())?;
+        let kind = kind.or_else(|| ForgeKind::detect(&host))?;
+        let path = path.trim_matches('/').trim_end_matches(".git");
+        if path.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            kind,
+            base_url: format!("https://{}/{}", host, path),
+        })
+    }
+
+    /// `https://host` part of the base URL.
+    pub fn web_root(&self) -> &str {
+        self.split_base().0
+    }
+
+    /// Repository path on the forge, e.g. `owner/repo` or `group/sub/repo`.
+    pub fn project_path(&self) -> &str {
+        self.split_base().1.trim_start_matches('/')
+    }
+
+    fn split_base(&self) -> (&str, &str) {
+        let scheme_end = self.base_url.find("://").map_or(0, |i| i + 3);
+        match self.base_url[scheme_end..].find('/') {
+            Some(i) => self.base_url.split_at(scheme_end + i),
+            None => (self.base_url.as_str(), ""),
+        }
+    }
+
+    pub fn compare_url(&self, from: &str, to: &str) -> String {
+        match self.kind {
+            ForgeKind::GitHub | ForgeKind::Gitea => {
+                format!("{}/compare/{}...{}", self.base_url, from, to)
+            }
+            ForgeKind::GitLab => format!("{}/-/compare/{}...{}", self.base_url, from, to),
+            ForgeKind::Bitbucket => {
+                format!("{}/branches/compare/{}%0D{}", self.base_url, to, from)
+            }
+        }
+    }
+
+    pub fn commit_url(&self, hash: &str) -> String {
+        match self.kind {
+            ForgeKind::GitHub | ForgeKind::Gitea => format!("{}/commit/{}", self.base_url, hash),
+            ForgeKind::GitLab => format!("{}/-/commit/{}", self.base_url, hash),
+            ForgeKind::Bitbucket => format!("{}/commits/{}", self.base_url, hash),
+        }
+    }
+
+    pub fn tag_url(&self, tag: &str) -> String {
+        match self.kind {
+            ForgeKind::GitHub | ForgeKind::Gitea => {
+                format!("{}/releases/tag/{}", self.base_url, tag)
+            }
+            ForgeKind::GitLab => format!("{}/-/tags/{}", self.base_url, tag),
+            ForgeKind::Bitbucket => format!("{}/src/{}", self.base_url, tag),
+        }
+    }
+}
+
+fn split_remote(remote: &str) -> Option<(String, String)> {
+    if let Some((_, rest)) = remote.split_once("://") {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+        return Some((host.to_string(), path.to_string()));
+    }
+
+    // scp-like syntax: [user@]host:owner/repo.git
+    let (authority, path) = remote.split_once(':')?;
+    let host = authority.rsplit('@').next()?;
+    Some((host.to_string(), path.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_remote_shapes() {
+        for remote in [
+            "git@github.com:o/r.git",
+            "https://github.com/o/r",
+            "ssh://git@github.com:22/o/r.git",
+            "https://token@github.com/o/r.git",
+        ] {
+            let forge = Forge::from_remote(remote, None).expect("known forge");
+            assert_eq!(forge.kind, ForgeKind::GitHub);
+            assert_eq!(forge.base_url, "https://github.com/o/r");
+        }
+
+        assert!(Forge::from_remote("https://example.com/o/r", None).is_none());
+        assert_eq!(
+            Forge::from_remote("https://example.com/o/r", Some(ForgeKind::Gitea))
+                .expect("explicit kind")
+                .kind,
+            ForgeKind::Gitea
+        );
+    }
+
+    #[test]
+    fn builds_compare_urls_per_forge() {
+        let gitlab = Forge::from_remote("git@gitlab.com:group/sub/r.git", None).expect("gitlab");
+        assert_eq!(
+            gitlab.compare_url("v1.0.0", "v1.1.0"),
+            "https://gitlab.com/group/sub/r/-/compare/v1.0.0...v1.1.0"
+        );
+
+        let bitbucket = Forge::from_remote("https://bitbucket.org/o/r.git", None).expect("bb");
+        assert_eq!(
+            bitbucket.compare_url("v1.0.0", "v1.1.0"),
+            "https://bitbucket.org/o/r/branches/compare/v1.1.0%0Dv1.0.0"
+        );
+
+        assert_eq!(gitlab.project_path(), "group/sub/r");
+        assert_eq!(gitlab.web_root(), "https://gitlab.com");
+
+        let gitea = Forge::from_remote("https://codeberg.org/o/r", None).expect("gitea");
+        assert_eq!(
+            gitea.tag_url("v1.0.0"),
+            "https://codeberg.org/o/r/releases/tag/v1.0.0"
+        );
+    }
+}
@@ -1,18 +1,47 @@
 use crate::domain::commit::Commit;
 use anyhow::{anyhow, Result};
-use git2::{ObjectType, Oid, Repository, Sort};
+use git2::{ErrorCode, ObjectType, Oid, Repository, Sort, Status, StatusOptions};
 use semver::Version;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Default)]
 pub struct CommitRange {
     pub since: Option<String>,
     pub until: Option<String>,
     pub specific: Option<String>,
+    /// Repository-relative pathspecs; when set, only commits whose diff
+    /// touches a matching file are listed.
+    pub include_paths: Vec<String>,
+    /// Pathspecs for files that never count as a change.
+    pub exclude_paths: Vec<String>,
+}
+
+impl CommitRange {
+    fn filters_paths(&self) -> bool {
+        !self.include_paths.is_empty() || !self.exclude_paths.is_empty()
+    }
+
+    /// Whether any of `paths` is included and not excluded.
+    pub fn matches_paths(&self, paths: &[PathBuf]) -> bool {
+        paths.iter().any(|path| {
+            let included = self.include_paths.is_empty()
+                || self
+                    .include_paths
+                    .iter()
+                    .any(|spec| pathspec_matches(spec, path));
+            included
+                && !self
+                    .exclude_paths
+                    .iter()
+                    .any(|spec| pathspec_matches(spec, path))
+        })
+    }
 }
 
 pub trait RepositoryApi {
     fn list_commits(&self, range: &CommitRange) -> Result<Vec<Commit>>;
     fn list_tags(&self) -> Result<Vec<String>>;
+    fn remote_url(&self, name: &str) -> Result<Option<String>>;
 }
 
 pub struct Git2Repository {
@@ -25,6 +54,60 @@ impl Git2Repository {
         Ok(Self { repo })
     }
 
+    pub fn workdir(&self) -> Option<&Path> {
+        self.repo.workdir()
+    }
+
+    /// Whether tracked files have staged or unstaged changes. Untracked files
+    /// do not count.
+    pub fn is_dirty(&self) -> Result<bool> {
+        let mut options = StatusOptions::new();
+        options.include_untracked(false).include_ignored(false);
+        let statuses = self.repo.statuses(Some(&mut options))?;
+        Ok(statuses
+            .iter()
+            .any(|entry| entry.status() != Status::CURRENT))
+    }
+
+    /// Stages `paths` (relative to the repository root) and commits them on
+    /// top of HEAD with the configured user as author. Returns the commit id.
+    pub fn commit_files(&self, paths: &[PathBuf], message: &str) -> Result<String> {
+        let mut index = self.repo.index()?;
+        for path in paths {
+            index.add_path(path)?;
+        }
+        index.write()?;
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+
+        let parent = match self.repo.head() {
+            Ok(head) => Some(head.peel_to_commit()?),
+            Err(e) if e.code() == ErrorCode::UnbornBranch || e.code() == ErrorCode::NotFound => {
+                None
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+
+        let signature = self.repo.signature()?;
+        let oid = self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?;
+        Ok(oid.to_string())
+    }
+
+    /// Creates the annotated tag `name` on HEAD; fails if it already exists.
+    pub fn create_annotated_tag(&self, name: &str, message: &str) -> Result<()> {
+        let head = self.repo.head()?.peel(ObjectType::Commit)?;
+        let signature = self.repo.signature()?;
+        self.repo.tag(name, &head, &signature, message, false)?;
+        Ok(())
+    }
+
     fn resolve_oid(&self, reference: &str) -> Result<Oid> {
         let object = self.repo.revparse_single(reference)?;
         Ok(object.id())
@@ -38,24 +121,52 @@ impl Git2Repository {
         Ok(target)
     }
 
-    fn collect_commits(&self, mut revwalk: git2::Revwalk<'_>) -> Result<Vec<Commit>> {
+    fn collect_commits(
+        &self,
+        mut revwalk: git2::Revwalk<'_>,
+        range: &CommitRange,
+    ) -> Result<Vec<Commit>> {
         revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
 
         let mut items = Vec::new();
         for oid in revwalk {
             let oid = oid?;
             let commit = self.repo.find_commit(oid)?;
-            let first_line = commit.summary().unwrap_or("").to_string();
-            items.push(Commit {
-                message: first_line,
-                hash: oid.to_string(),
-            });
+            if range.filters_paths() && !range.matches_paths(&self.commit_paths(&commit)?) {
+                continue;
+            }
+            items.push(to_domain_commit(&commit));
         }
 
         // Deterministic oldest -> newest output for note grouping.
         items.reverse();
         Ok(items)
     }
+
+    /// Paths changed by `commit`, from a tree diff against its first parent
+    /// (or the empty tree for a root commit).
+    fn commit_paths(&self, commit: &git2::Commit<'_>) -> Result<Vec<PathBuf>> {
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent_count() {
+            0 => None,
+            _ => Some(commit.parent(0)?.tree()?),
+        };
+
+        let diff = self
+            .repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        let mut paths = Vec::new();
+        for delta in diff.deltas() {
+            for file in [delta.old_file(), delta.new_file()] {
+                if let Some(path) = file.path() {
+                    if !paths.iter().any(|p: &PathBuf| p == path) {
+                        paths.push(path.to_path_buf());
+                    }
+                }
+            }
+        }
+        Ok(paths)
+    }
 }
 
 impl RepositoryApi for Git2Repository {
@@ -69,7 +180,7 @@ impl RepositoryApi for Git2Repository {
             for parent in commit.parents() {
                 revwalk.hide(parent.id())?;
             }
-            return self.collect_commits(revwalk);
+            return self.collect_commits(revwalk, range);
         }
 
         let until_oid = match &range.until {
@@ -83,7 +194,7 @@ impl RepositoryApi for Git2Repository {
             revwalk.hide(since_oid)?;
         }
 
-        self.collect_commits(revwalk)
+        self.collect_commits(revwalk, range)
     }
 
     fn list_tags(&self) -> Result<Vec<String>> {
@@ -105,6 +216,30 @@ impl RepositoryApi for Git2Repository {
         tags.sort();
         Ok(tags)
     }
+
+    fn remote_url(&self, name: &str) -> Result<Option<String>> {
+        match self.repo.find_remote(name) {
+            Ok(remote) => Ok(remote.url().map(str::to_string)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Converts a git2 commit into the domain type, keeping the full message,
+/// author, timestamps and parent count.
+pub fn to_domain_commit(commit: &git2::Commit<'_>) -> Commit {
+    let message = String::from_utf8_lossy(commit.message_bytes());
+    let author = commit.author();
+
+    Commit {
+        author_name: author.name().unwrap_or_default().to_string(),
+        author_email: author.email().unwrap_or_default().to_string(),
+        authored_at: author.when().seconds(),
+        committed_at: commit.time().seconds(),
+        parent_count: commit.parent_count(),
+        ..Commit::new(commit.id().to_string(), &message)
+    }
 }
 
 pub fn latest_semver_tag(tags: &[String]) -> Option<String> {
@@ -122,6 +257,75 @@ pub fn latest_semver_tag(tags: &[String]) -> Option<String> {
     parsed.first().map(|item| item.1.clone())
 }
 
+/// Matches a repository-relative path against a pathspec: either a
+/// directory or file prefix (`src/`, `docs/guide.md`) or a pattern with `*`
+/// and `?` (`*.md`, `crates/*/src`) matched against the path and each of its
+/// parent directories.
+pub fn pathspec_matches(spec: &str, path: &Path) -> bool {
+    let spec = spec.trim_start_matches("./").trim_end_matches('/');
+    if spec.is_empty() || spec == "." {
+        return true;
+    }
+
+    if spec.contains(['*', '?']) {
+        return path
+            .ancestors()
+            .any(|dir| wildcard_match(spec, &dir.to_string_lossy()))
+            || path
+                .file_name()
+                .is_some_and(|name| wildcard_match(spec, &name.to_string_lossy()));
+    }
+
+    path.starts_with(spec)
+}
+
+/// Matches `*` (any run of characters) and `?` (one character).
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0usize, 0usize);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Latest tag named `<prefix><semver>`, e.g. `mycrate-v1.2.0` for prefix
+/// `mycrate-v`.
+/// Version a tag names: the tag without `prefix` (or a plain `v`) as SemVer.
+pub fn tag_version(tag: &str, prefix: &str) -> Option<Version> {
+    let raw = tag
+        .strip_prefix(prefix)
+        .or_else(|| tag.strip_prefix('v'))
+        .unwrap_or(tag);
+    Version::parse(raw).ok()
+}
+
+pub fn latest_tag_with_prefix(tags: &[String], prefix: &str) -> Option<String> {
+    tags.iter()
+        .filter_map(|tag| {
+            let version = Version::parse(tag.strip_prefix(prefix)?).ok()?;
+            Some((version, tag))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, tag)| tag.clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +340,49 @@ mod tests {
 
         assert_eq!(latest_semver_tag(&tags), Some("v1.10.0".to_string()));
     }
+
+    #[test]
+    fn picks_latest_tag_for_prefix() {
+        let tags = vec![
+            "core-v1.2.0".to_string(),
+            "core-v1.10.0".to_string(),
+            "cli-v2.0.0".to_string(),
+            "v3.0.0".to_string(),
+        ];
+
+        assert_eq!(
+            latest_tag_with_prefix(&tags, "core-v"),
+            Some("core-v1.10.0".to_string())
+        );
+        assert_eq!(latest_tag_with_prefix(&tags, "api-v"), None);
+
+        assert_eq!(
+            tag_version("core-v1.10.0", "core-v"),
+            Some(Version::new(1, 10, 0))
+        );
+        assert_eq!(
+            tag_version("v0.1.0", "release-"),
+            Some(Version::new(0, 1, 0))
+        );
+        assert_eq!(tag_version("nightly", "v"), None);
+    }
+
+    #[test]
+    fn matches_pathspecs() {
+        let path = Path::new("crates/core/src/lib.rs");
+        assert!(pathspec_matches("crates/core", path));
+        assert!(pathspec_matches("./crates/core/", path));
+        assert!(pathspec_matches("crates/*/src", path));
+        assert!(pathspec_matches("*.rs", path));
+        assert!(!pathspec_matches("crates/cli", path));
+        assert!(!pathspec_matches("crates/co", path));
+
+        let range = CommitRange {
+            include_paths: vec!["crates/".to_string()],
+            exclude_paths: vec!["*.md".to_string()],
+            ..CommitRange::default()
+        };
+        assert!(range.matches_paths(&[PathBuf::from("README.md"), path.to_path_buf()]));
+        assert!(!range.matches_paths(&[PathBuf::from("crates/core/README.md")]));
+    }
 }
@@ -0,0 +1,144 @@
+//! Standalone HTML rendering of a changelog.
+
+use crate::core::changelog::{ChangelogDocument, Release};
+use pulldown_cmark::{html, Event, Options, Parser};
+
+const STYLE: &str = "\
+body{margin:0;background:#f6f8fa;color:#1f2328;font:16px/1.6 system-ui,-apple-system,'Segoe UI',sans-serif}
+main{max-width:48rem;margin:0 auto;padding:2rem 1.5rem 4rem}
+h1{font-size:2rem;margin:0 0 1rem}
+.release{background:#fff;border:1px solid #d0d7de;border-radius:8px;padding:.5rem 1.5rem;margin:1.5rem 0}
+.release h2{display:flex;align-items:baseline;gap:.75rem;font-size:1.4rem}
+.release h2 a{color:inherit;text-decoration:none}
+.release h2 a:hover{text-decoration:underline}
+.release time,.release .compare{color:#656d76;font-size:.9rem;font-weight:normal}
+h3{font-size:1.05rem;margin:1.25rem 0 .25rem}
+ul{padding-left:1.25rem}
+code{background:#eff1f3;border-radius:4px;padding:.1em .3em;font-size:.9em}
+a{color:#0969da}";
+
+/// A self-contained page with one `<section id="<version>">` per release.
+pub fn page(doc: &ChangelogDocument) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(&doc.title)));
+    out.push_str(&format!("<style>\n{}\n</style>\n", STYLE));
+    out.push_str("</head>\n<body>\n<main>\n");
+    out.push_str(&format!("<h1>{}</h1>\n", escape(&doc.title)));
+    if !doc.preamble.is_empty() {
+        out.push_str(&markdown_to_html(&with_link_definitions(
+            doc,
+            &doc.preamble,
+        )));
+    }
+
+    for release in &doc.releases {
+        let id = anchor(release);
+        out.push_str(&format!(
+            "<section class=\"release\" id=\"{}\">\n",
+            escape(&id)
+        ));
+        out.push_str(&format!(
+            "<h2><a href=\"#{}\">{}</a>",
+            escape(&id),
+            escape(&release.version.to_string())
+        ));
+        if let Some(date) = &release.date {
+            out.push_str(&format!(" <time datetime=\"{0}\">{0}</time>", escape(date)));
+        }
+        let label = release.version.to_string();
+        if let Some(link) = doc
+            .links
+            .iter()
+            .find(|link| link.label.eq_ignore_ascii_case(&label))
+        {
+            out.push_str(&format!(
+                " <a class=\"compare\" href=\"{}\">compare</a>",
+                escape(&link.url)
+            ));
+        }
+        out.push_str("</h2>\n");
+        out.push_str(&release_html(doc, release));
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</main>\n</body>\n</html>\n");
+    out
+}
+
+/// The description and sections of `release` as HTML. Reference-style
+/// links resolve against the document's footer definitions.
+pub fn release_html(doc: &ChangelogDocument, release: &Release) -> String {
+    markdown_to_html(&with_link_definitions(doc, &release.body_markdown()))
+}
+
+/// Fragment identifier of a release: its version, or `unreleased`.
+pub fn anchor(release: &Release) -> String {
+    release.version.to_string().to_ascii_lowercase()
+}
+
+/// Escapes text for HTML and XML content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn with_link_definitions(doc: &ChangelogDocument, markdown: &str) -> String {
+    let mut out = markdown.to_string();
+    if !doc.links.is_empty() {
+        out.push_str("\n\n");
+        for link in &doc.links {
+            out.push_str(&format!("[{}]: {}\n", link.label, link.url));
+        }
+    }
+    out
+}
+
+/// Renders Markdown, showing raw HTML (e.g. `Vec<T>` in a commit message)
+/// as text.
+fn markdown_to_html(markdown: &str) -> String {
+    let parser =
+        Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH).map(|event| match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            event => event,
+        });
+    let mut out = String::new();
+    html::push_html(&mut out, parser);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_page_with_anchors() {
+        let doc = ChangelogDocument::parse(
+            "# Changelog\n\n## [1.1.0] - 2026-01-01\n\n### Added\n- **api:** export <b> ([#12](https://example.com/pull/12))\n  - nested\n\n[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0\n",
+        )
+        .expect("parse changelog");
+
+        let html = page(&doc);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Changelog</title>"));
+        assert!(html.contains(
+            "<section class=\"release\" id=\"1.1.0\">\n<h2><a href=\"#1.1.0\">1.1.0</a> <time datetime=\"2026-01-01\">2026-01-01</time> <a class=\"compare\" href=\"https://example.com/compare/v1.0.0...v1.1.0\">compare</a></h2>"
+        ));
+        assert!(html.contains("<h3>Added</h3>"));
+        assert!(html.contains(
+            "<strong>api:</strong> export &lt;b&gt; (<a href=\"https://example.com/pull/12\">#12</a>)"
+        ));
+        assert!(html.contains("<li>nested</li>"));
+    }
+}
@@ -0,0 +1,334 @@
+//! AsciiDoc and reStructuredText backends.
+//!
+//! Both translate to and from the Markdown subset that `ChangelogDocument`
+//! parses and renders, so every format shares the same parser and
+//! validation rules, and parse errors point at the line of the original file.
+
+use crate::core::changelog::parse_link_definition;
+use regex::{Captures, Regex};
+
+/// A changelog markup that is translated line by line from and to Markdown.
+pub trait MarkupBackend {
+    /// `input` rewritten as Markdown, keeping line numbers where possible.
+    fn to_markdown(&self, input: &str) -> String;
+    /// Output of `ChangelogDocument::to_markdown` rewritten in this markup.
+    fn render_markdown(&self, markdown: &str) -> String;
+}
+
+/// `= Title`, `== [1.0.0] - date`, `=== Added` and `*` / `**` bullets.
+/// Footer link definitions are kept as `// [label]: url` comments.
+pub struct AsciiDoc;
+
+/// Underlined headings (`=`, `-`, `~`) and `-` bullets. Footer link
+/// definitions become `.. _label: url` hyperlink targets.
+pub struct ReStructuredText;
+
+impl MarkupBackend for AsciiDoc {
+    fn to_markdown(&self, input: &str) -> String {
+        let heading = Regex::new(r"^(=+)\s+(.*)$").expect("valid regex");
+        let bullet = Regex::new(r"^(\*+|-)\s+(.*)$").expect("valid regex");
+
+        let lines = input.lines().map(|line| {
+            let trimmed = line.trim_start();
+            if let Some(caps) = heading.captures(line) {
+                return format!(
+                    "{} {}",
+                    "#".repeat(caps[1].len()),
+                    asciidoc_links_to_markdown(caps[2].trim_end())
+                );
+            }
+            if let Some(comment) = trimmed.strip_prefix("//") {
+                let comment = comment.trim();
+                return match parse_link_definition(comment) {
+                    Some(_) => comment.to_string(),
+                    None => String::new(),
+                };
+            }
+            if let Some(caps) = bullet.captures(trimmed) {
+                let level = if &caps[1] == "-" { 1 } else { caps[1].len() };
+                return format!(
+                    "{}- {}",
+                    "  ".repeat(level - 1),
+                    asciidoc_links_to_markdown(&caps[2])
+                );
+            }
+            asciidoc_links_to_markdown(line)
+        });
+        join_lines(lines)
+    }
+
+    fn render_markdown(&self, markdown: &str) -> String {
+        let lines = markdown.lines().map(|line| {
+            if let Some((level, text)) = markdown_heading(line) {
+                return format!("{} {}", "=".repeat(level), markdown_links_to_asciidoc(text));
+            }
+            if !line.starts_with(' ') && parse_link_definition(line).is_some() {
+                return format!("// {}", line);
+            }
+            if let Some((indent, text)) = markdown_bullet(line) {
+                return format!(
+                    "{} {}",
+                    "*".repeat(indent / 2 + 1),
+                    markdown_links_to_asciidoc(text)
+                );
+            }
+            markdown_links_to_asciidoc(line)
+        });
+        join_lines(lines)
+    }
+}
+
+impl MarkupBackend for ReStructuredText {
+    fn to_markdown(&self, input: &str) -> String {
+        let target = Regex::new(r"^\.\. _`?([^`:]+)`?:\s+(\S+)\s*$").expect("valid regex");
+        let bullet = Regex::new(r"^(\s*)[-*+]\s+(.*)$").expect("valid regex");
+
+        let lines: Vec<&str> = input.lines().collect();
+        // Heading levels follow the order adornment styles first appear in.
+        let mut styles: Vec<(char, bool)> = Vec::new();
+        let mut level_of = |style: (char, bool)| match styles.iter().position(|s| *s == style) {
+            Some(index) => index + 1,
+            None => {
+                styles.push(style);
+                styles.len()
+            }
+        };
+
+        let mut out: Vec<String> = Vec::new();
+        let mut idx = 0;
+        while idx < lines.len() {
+            let line = lines[idx];
+            let next = lines.get(idx + 1).copied();
+
+            if let (Some(over), Some(title), Some(under)) = (
+                adornment(line),
+                next,
+                lines.get(idx + 2).and_then(|l| adornment(l)),
+            ) {
+                if over == under && !title.trim().is_empty() {
+                    let level = level_of((over, true));
+                    out.push(String::new());
+                    out.push(format!("{} {}", "#".repeat(level), title.trim()));
+                    out.push(String::new());
+                    idx += 3;
+                    continue;
+                }
+            }
+
+            let is_title = !line.trim().is_empty()
+                && !line.starts_with(char::is_whitespace)
+                && adornment(line).is_none();
+            if let Some(under) = next.filter(|_| is_title).and_then(adornment) {
+                if next.map_or(0, |n| n.trim_end().chars().count()) >= line.trim().chars().count() {
+                    let level = level_of((under, false));
+                    out.push(format!(
+                        "{} {}",
+                        "#".repeat(level),
+                        rst_inline_to_markdown(line.trim())
+                    ));
+                    out.push(String::new());
+                    idx += 2;
+                    continue;
+                }
+            }
+
+            if let Some(caps) = target.captures(line) {
+                out.push(format!("[{}]: {}", caps[1].trim(), &caps[2]));
+            } else if line.starts_with("..") {
+                out.push(String::new());
+            } else if let Some(caps) = bullet.captures(line) {
+                out.push(format!(
+                    "{}- {}",
+                    &caps[1],
+                    rst_inline_to_markdown(&caps[2])
+                ));
+            } else if line.trim().is_empty() {
+                // Nested lists and continuation paragraphs need a blank line
+                // in reStructuredText; in Markdown they would split the note.
+                let in_list = out.last().is_some_and(|prev| {
+                    prev.trim_start().starts_with("- ") || prev.starts_with(char::is_whitespace)
+                });
+                let continues = lines[idx + 1..]
+                    .iter()
+                    .find(|l| !l.trim().is_empty())
+                    .is_some_and(|l| l.starts_with(char::is_whitespace));
+                if !(in_list && continues) {
+                    out.push(String::new());
+                }
+            } else {
+                out.push(rst_inline_to_markdown(line));
+            }
+            idx += 1;
+        }
+        join_lines(out.into_iter())
+    }
+
+    fn render_markdown(&self, markdown: &str) -> String {
+        const ADORNMENTS: [char; 5] = ['=', '-', '~', '^', '"'];
+
+        let lines: Vec<&str> = markdown.lines().collect();
+        let mut out: Vec<String> = Vec::new();
+        // Indentation of the bullet text of the list item being written.
+        let mut list_indent: Option<usize> = None;
+        let push_blank = |out: &mut Vec<String>| {
+            if out.last().is_some_and(|prev| !prev.is_empty()) {
+                out.push(String::new());
+            }
+        };
+
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some((level, text)) = markdown_heading(line) {
+                let text = markdown_inline_to_rst(text);
+                let adornment = ADORNMENTS[(level - 1).min(ADORNMENTS.len() - 1)];
+                push_blank(&mut out);
+                out.push(text.clone());
+                out.push(adornment.to_string().repeat(text.chars().count().max(1)));
+                if lines
+                    .get(idx + 1)
+                    .is_some_and(|next| !next.trim().is_empty())
+                {
+                    out.push(String::new());
+                }
+                list_indent = None;
+            } else if let Some(label_url) =
+                parse_link_definition(line).filter(|_| !line.starts_with(' '))
+            {
+                out.push(format!(".. _{}: {}", label_url.label, label_url.url));
+                list_indent = None;
+            } else if let Some((indent, text)) = markdown_bullet(line) {
+                // Nested lists are separated from their parent item by
+                // blank lines, on the way in and on the way out.
+                if list_indent.is_some_and(|current| indent + 2 != current) {
+                    push_blank(&mut out);
+                }
+                out.push(format!(
+                    "{}- {}",
+                    " ".repeat(indent),
+                    markdown_inline_to_rst(text)
+                ));
+                list_indent = Some(indent + 2);
+            } else if line.trim().is_empty() {
+                out.push(String::new());
+                list_indent = None;
+            } else if let Some(indent) = list_indent {
+                // Continuation lines must line up with the bullet text.
+                let own = line.len() - line.trim_start().len();
+                let pad = " ".repeat(indent.saturating_sub(own));
+                out.push(format!("{}{}", pad, markdown_inline_to_rst(line)));
+            } else {
+                out.push(markdown_inline_to_rst(line));
+            }
+        }
+        join_lines(out.into_iter())
+    }
+}
+
+fn join_lines(lines: impl Iterator<Item = String>) -> String {
+    let text = lines.collect::<Vec<_>>().join("\n");
+    text.trim_end().to_string() + "\n"
+}
+
+/// `(level, text)` of a `#`-style Markdown heading.
+fn markdown_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (level > 0).then_some((level, text.trim()))
+}
+
+/// `(indent, text)` of a `- ` Markdown bullet.
+fn markdown_bullet(line: &str) -> Option<(usize, &str)> {
+    let text = line.trim_start();
+    let indent = line.len() - text.len();
+    text.strip_prefix("- ").map(|text| (indent, text))
+}
+
+/// The adornment character of a reStructuredText heading underline or
+/// overline, e.g. `=====`.
+fn adornment(line: &str) -> Option<char> {
+    const CHARS: &str = "=-~^\"'`#*+:_";
+    let line = line.trim_end();
+    let first = line.chars().next()?;
+    (CHARS.contains(first) && line.chars().all(|c| c == first)).then_some(first)
+}
+
+fn markdown_links_to_asciidoc(text: &str) -> String {
+    let link = Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").expect("valid regex");
+    link.replace_all(text, "$2[$1]").to_string()
+}
+
+fn asciidoc_links_to_markdown(text: &str) -> String {
+    let link = Regex::new(r"(https?://[^\s\[]+)\[([^\]]*)\]").expect("valid regex");
+    link.replace_all(text, |caps: &Captures| {
+        let label = if caps[2].is_empty() {
+            &caps[1]
+        } else {
+            &caps[2]
+        };
+        format!("[{}]({})", label, &caps[1])
+    })
+    .to_string()
+}
+
+fn markdown_inline_to_rst(text: &str) -> String {
+    let code = Regex::new(r"`([^`]+)`").expect("valid regex");
+    let link = Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").expect("valid regex");
+    let text = code.replace_all(text, "``$1``");
+    link.replace_all(&text, "`$1 <$2>`__").to_string()
+}
+
+fn rst_inline_to_markdown(text: &str) -> String {
+    let link = Regex::new(r"`([^`<]*?)\s*<([^>`]+)>`__?").expect("valid regex");
+    let code = Regex::new(r"``([^`]+)``").expect("valid regex");
+    let text = link.replace_all(text, |caps: &Captures| {
+        let label = if caps[1].is_empty() {
+            &caps[2]
+        } else {
+            &caps[1]
+        };
+        format!("[{}]({})", label, &caps[2])
+    });
+    code.replace_all(&text, "`$1`").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKDOWN: &str = "# Changelog\n\nAll notable changes.\n\n## [Unreleased]\n\n### Added\n- wip\n\n## [1.1.0] - 2026-01-01\n\n### Added\n- **api:** export ([#12](https://example.com/pull/12))\n  - nested `--flag`\n- second\n\n### Fixed\n- crash\n\n[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0\n";
+
+    #[test]
+    fn renders_asciidoc() {
+        let adoc = AsciiDoc.render_markdown(MARKDOWN);
+        assert!(adoc.starts_with("= Changelog\n\nAll notable changes.\n\n== [Unreleased]\n"));
+        assert!(adoc.contains(
+            "=== Added\n* **api:** export (https://example.com/pull/12[#12])\n** nested `--flag`\n* second\n"
+        ));
+        assert!(adoc.ends_with("// [1.1.0]: https://example.com/compare/v1.0.0...v1.1.0\n"));
+        assert_eq!(AsciiDoc.to_markdown(&adoc), MARKDOWN);
+    }
+
+    #[test]
+    fn renders_restructuredtext() {
+        let rst = ReStructuredText.render_markdown(MARKDOWN);
+        assert!(rst.starts_with("Changelog\n=========\n\nAll notable changes.\n\n[Unreleased]\n------------\n\nAdded\n~~~~~\n\n- wip\n"));
+        assert!(rst.contains(
+            "- **api:** export (`#12 <https://example.com/pull/12>`__)\n\n  - nested ``--flag``\n\n- second\n"
+        ));
+        assert!(rst.ends_with(".. _1.1.0: https://example.com/compare/v1.0.0...v1.1.0\n"));
+
+        let markdown = ReStructuredText.to_markdown(&rst);
+        assert!(markdown.contains(
+            "### Added\n\n\n- **api:** export ([#12](https://example.com/pull/12))\n  - nested `--flag`\n\n- second\n"
+        ));
+        assert!(markdown.ends_with("[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0\n"));
+    }
+
+    #[test]
+    fn reads_restructuredtext_heading_styles_in_order() {
+        let rst = "=========\nChangelog\n=========\n\n1.0.0\n*****\n\nFixed\n#####\n\n* crash\n";
+        assert_eq!(
+            ReStructuredText.to_markdown(rst),
+            "\n# Changelog\n\n\n## 1.0.0\n\n\n### Fixed\n\n\n- crash\n"
+        );
+    }
+}
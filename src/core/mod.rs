@@ -1,3 +1,12 @@
+pub mod bump;
 pub mod changelog;
+pub mod commit_parser;
+pub mod context;
+pub mod feed;
+pub mod forge;
 pub mod git;
+pub mod html;
+pub mod markup;
 pub mod notes;
+pub mod packaging;
+pub mod processors;
@@ -1,6 +1,10 @@
+use crate::core::commit_parser::CommitParsers;
 use crate::domain::commit::Commit;
 use std::collections::{BTreeMap, BTreeSet};
 
+/// Mapping key for the section that collects breaking changes.
+pub const BREAKING_KIND: &str = "breaking";
+
 pub const IGNORE_MARKERS: [&str; 4] = [
     "(skip changelog)",
     "(ignore changelog)",
@@ -8,10 +12,85 @@ pub const IGNORE_MARKERS: [&str; 4] = [
     "!log",
 ];
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Note {
     pub section: String,
     pub text: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    /// Commit the note was written from; `None` for milestone items.
+    pub hash: Option<String>,
+    pub author: Option<String>,
+    pub pull_request: Option<PullRequestLink>,
+}
+
+/// Pull request a note is credited to, rendered after its text.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PullRequestLink {
+    pub number: u64,
+    pub url: String,
+    /// Login of the pull request author.
+    pub author: Option<String>,
+}
+
+impl Note {
+    /// Text with the pull request link and credit appended, e.g.
+    /// `add export ([#12](https://...)) by @alice`.
+    pub fn linked_text(&self) -> String {
+        let Some(pr) = &self.pull_request else {
+            return self.text.clone();
+        };
+        let mut text = format!("{} ([#{}]({}))", self.text, pr.number, pr.url);
+        if let Some(author) = &pr.author {
+            text.push_str(&format!(" by @{}", author));
+        }
+        text
+    }
+}
+
+/// How the scope of a note is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScopeStyle {
+    /// `**api:** description`
+    #[default]
+    Inline,
+    /// One `**api:**` bullet per scope with its notes nested below.
+    Grouped,
+    /// Scopes are dropped.
+    Hidden,
+}
+
+impl ScopeStyle {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "inline" => Some(ScopeStyle::Inline),
+            "grouped" => Some(ScopeStyle::Grouped),
+            "none" => Some(ScopeStyle::Hidden),
+            _ => None,
+        }
+    }
+}
+
+/// Scope selection for `generate --scope` / `--exclude-scope`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScopeFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl ScopeFilter {
+    /// With an include list, only notes with one of those scopes pass;
+    /// excluded scopes never pass.
+    pub fn allows(&self, scope: Option<&str>) -> bool {
+        let listed = |list: &[String]| {
+            scope.is_some_and(|scope| list.iter().any(|s| s.eq_ignore_ascii_case(scope)))
+        };
+
+        if !self.include.is_empty() && !listed(&self.include) {
+            return false;
+        }
+        !listed(&self.exclude)
+    }
 }
 
 pub fn should_ignore_commit(message: &str) -> bool {
@@ -19,7 +98,25 @@ pub fn should_ignore_commit(message: &str) -> bool {
     IGNORE_MARKERS.iter().any(|marker| lower.contains(marker))
 }
 
-pub fn parse_conventional_commit(message: &str) -> Option<(String, String)> {
+/// Whether `message` contains one of `markers`, ignoring case.
+pub fn has_ignore_marker(message: &str, markers: &[String]) -> bool {
+    let lower = message.to_lowercase();
+    markers
+        .iter()
+        .filter(|marker| !marker.is_empty())
+        .any(|marker| lower.contains(&marker.to_lowercase()))
+}
+
+/// Parsed `type(scope)!: description` header of a conventional commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalHeader {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+pub fn parse_conventional_header(message: &str) -> Option<ConventionalHeader> {
     let trimmed = message.trim();
     let (head, tail) = trimmed.split_once(':')?;
     let description = tail.trim();
@@ -27,14 +124,33 @@ pub fn parse_conventional_commit(message: &str) -> Option<(String, String)> {
         return None;
     }
 
-    let kind = head.split('(').next().unwrap_or(head).trim();
-    let kind = kind.trim_end_matches('!');
+    let head = head.trim();
+    let breaking = head.ends_with('!');
+    let head = head.trim_end_matches('!');
+
+    let (kind, scope) = match head.split_once('(') {
+        Some((kind, rest)) => {
+            let scope = rest.strip_suffix(')')?.trim();
+            let scope = (!scope.is_empty()).then(|| scope.to_string());
+            (kind.trim(), scope)
+        }
+        None => (head, None),
+    };
 
-    if kind.is_empty() {
+    if kind.is_empty() || kind.contains(char::is_whitespace) {
         return None;
     }
 
-    Some((kind.to_string(), description.to_string()))
+    Some(ConventionalHeader {
+        kind: kind.to_string(),
+        scope,
+        breaking,
+        description: description.to_string(),
+    })
+}
+
+pub fn parse_conventional_commit(message: &str) -> Option<(String, String)> {
+    parse_conventional_header(message).map(|header| (header.kind, header.description))
 }
 
 pub fn map_type_to_section(kind: &str, mapping: &BTreeMap<String, String>) -> String {
@@ -48,15 +164,54 @@ pub fn map_type_to_section(kind: &str, mapping: &BTreeMap<String, String>) -> St
         "perf" | "refactor" => "Changed".to_string(),
         "docs" => "Documentation".to_string(),
         "test" | "chore" | "build" | "ci" => "Maintenance".to_string(),
+        BREAKING_KIND => "Breaking Changes".to_string(),
         _ => "Other".to_string(),
     }
 }
 
+/// Section for an issue or pull request: the first label listed in
+/// `mapping`, else the first label with a built-in section.
+pub fn map_labels_to_section(
+    labels: &[String],
+    mapping: &BTreeMap<String, String>,
+) -> Option<String> {
+    if let Some(section) = labels.iter().find_map(|label| mapping.get(label)) {
+        return Some(section.clone());
+    }
+
+    labels.iter().find_map(|label| {
+        let section = match label.to_lowercase().as_str() {
+            "breaking" | "breaking change" | "breaking-change" => "Breaking Changes",
+            "bug" | "bugfix" | "fix" => "Fixed",
+            "enhancement" | "feature" => "Added",
+            "documentation" | "docs" => "Documentation",
+            "performance" | "refactor" => "Changed",
+            "dependencies" | "chore" | "ci" => "Maintenance",
+            _ => return None,
+        };
+        Some(section.to_string())
+    })
+}
+
 pub fn notes_from_commits(
     commits: &[Commit],
     mapping: &BTreeMap<String, String>,
 ) -> BTreeMap<String, Vec<String>> {
-    let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    group_notes(
+        &collect_notes(commits, mapping, &CommitParsers::default()),
+        ScopeStyle::default(),
+    )
+}
+
+/// Turns commits into section-tagged notes, keeping each note's scope.
+/// Summaries go through `parsers` first; commits no rule matches are
+/// parsed as conventional commits.
+pub fn collect_notes(
+    commits: &[Commit],
+    mapping: &BTreeMap<String, String>,
+    parsers: &CommitParsers,
+) -> Vec<Note> {
+    let mut notes = Vec::new();
 
     for commit in commits {
         if should_ignore_commit(&commit.message) {
@@ -74,15 +229,113 @@ pub fn notes_from_commits(
             continue;
         }
 
-        if let Some((kind, description)) = parse_conventional_commit(&text) {
-            let section = map_type_to_section(&kind, mapping);
-            grouped.entry(section).or_default().push(description);
-        } else {
-            grouped.entry("Other".to_string()).or_default().push(text);
+        let origin = Note {
+            hash: Some(commit.hash.clone()),
+            author: (!commit.author_name.is_empty()).then(|| commit.author_name.clone()),
+            ..Note::default()
+        };
+        let parsed = match parsers.parse(&text) {
+            Some(parsed) if parsed.skip => continue,
+            Some(parsed) => {
+                let kind = parsed.kind.unwrap_or_default();
+                let section = parsed
+                    .section
+                    .unwrap_or_else(|| map_type_to_section(&kind, mapping));
+                Some((
+                    section,
+                    ConventionalHeader {
+                        kind,
+                        scope: parsed.scope,
+                        breaking: parsed.breaking,
+                        description: parsed.description,
+                    },
+                ))
+            }
+            None => parse_conventional_header(&text)
+                .map(|header| (map_type_to_section(&header.kind, mapping), header)),
+        };
+        let Some((section, header)) = parsed else {
+            notes.push(Note {
+                section: "Other".to_string(),
+                text,
+                ..origin
+            });
+            continue;
+        };
+
+        let breaking = commit.breaking || header.breaking;
+        if breaking {
+            let note = commit
+                .breaking_description()
+                .unwrap_or(&header.description)
+                .to_string();
+            notes.push(Note {
+                section: map_type_to_section(BREAKING_KIND, mapping),
+                text: note,
+                scope: header.scope.clone(),
+                breaking,
+                ..origin.clone()
+            });
+        }
+
+        notes.push(Note {
+            section,
+            text: header.description,
+            scope: header.scope,
+            breaking,
+            ..origin
+        });
+    }
+
+    notes
+}
+
+/// Groups notes by section, rendering scopes per `style`, and dedupes.
+pub fn group_notes(notes: &[Note], style: ScopeStyle) -> BTreeMap<String, Vec<String>> {
+    let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    if style != ScopeStyle::Grouped {
+        for note in notes {
+            let text = match (&note.scope, style) {
+                (Some(scope), ScopeStyle::Inline) => {
+                    format!("**{}:** {}", scope, note.linked_text())
+                }
+                _ => note.linked_text(),
+            };
+            grouped.entry(note.section.clone()).or_default().push(text);
+        }
+        return dedupe_grouped_notes(&grouped);
+    }
+
+    // Unscoped notes stay plain bullets ahead of one nested group per scope.
+    let mut scoped: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    for note in notes {
+        match &note.scope {
+            Some(scope) => scoped
+                .entry(note.section.clone())
+                .or_default()
+                .entry(scope.clone())
+                .or_default()
+                .push(note.linked_text()),
+            None => grouped
+                .entry(note.section.clone())
+                .or_default()
+                .push(note.linked_text()),
         }
     }
 
-    dedupe_grouped_notes(&grouped)
+    let mut grouped = dedupe_grouped_notes(&grouped);
+    for (section, scopes) in scoped {
+        for (scope, texts) in dedupe_grouped_notes(&scopes) {
+            let mut note = format!("**{}:**", scope);
+            for text in texts {
+                note.push_str("\n  - ");
+                note.push_str(&text);
+            }
+            grouped.entry(section.clone()).or_default().push(note);
+        }
+    }
+    grouped
 }
 
 pub fn dedupe_grouped_notes(
@@ -146,4 +399,132 @@ mod tests {
             &vec!["new api".to_string(), "another item".to_string()]
         );
     }
+
+    #[test]
+    fn parses_scope_and_breaking_marker() {
+        let header = parse_conventional_header("feat(api)!: drop v1").expect("header");
+        assert_eq!(header.kind, "feat");
+        assert_eq!(header.scope.as_deref(), Some("api"));
+        assert!(header.breaking);
+        assert!(parse_conventional_header("Merge branch 'x': y").is_none());
+    }
+
+    #[test]
+    fn breaking_changes_get_their_own_section() {
+        let commits = vec![
+            Commit::new(
+                "a",
+                "feat!: new config format\n\nBREAKING CHANGE: rename `out` to `output`",
+            ),
+            Commit::new("b", "fix(cli)!: stricter flags"),
+            Commit::new("c", "fix: small thing"),
+        ];
+
+        let grouped = notes_from_commits(&commits, &BTreeMap::new());
+        assert_eq!(
+            grouped["Breaking Changes"],
+            vec![
+                "rename `out` to `output`".to_string(),
+                "**cli:** stricter flags".to_string()
+            ]
+        );
+        assert_eq!(grouped["Added"], vec!["new config format".to_string()]);
+        assert_eq!(grouped["Fixed"].len(), 2);
+    }
+
+    #[test]
+    fn renders_and_filters_scopes() {
+        let commits = vec![
+            Commit::new("a", "feat(api): add endpoint"),
+            Commit::new("b", "fix(api): handle timeout"),
+            Commit::new("c", "feat(cli): add flag"),
+            Commit::new("d", "chore(deps): bump serde"),
+            Commit::new("e", "feat: unscoped"),
+        ];
+        let notes = collect_notes(&commits, &BTreeMap::new(), &CommitParsers::default());
+
+        let inline = group_notes(&notes, ScopeStyle::Inline);
+        assert_eq!(inline["Added"][0], "**api:** add endpoint");
+
+        let grouped = group_notes(&notes, ScopeStyle::Grouped);
+        assert_eq!(
+            grouped["Added"],
+            vec![
+                "unscoped".to_string(),
+                "**api:**\n  - add endpoint".to_string(),
+                "**cli:**\n  - add flag".to_string()
+            ]
+        );
+
+        let filter = ScopeFilter {
+            include: vec!["api".to_string(), "deps".to_string()],
+            exclude: vec!["deps".to_string()],
+        };
+        let kept: Vec<&str> = notes
+            .iter()
+            .filter(|n| filter.allows(n.scope.as_deref()))
+            .map(|n| n.text.as_str())
+            .collect();
+        assert_eq!(kept, vec!["add endpoint", "handle timeout"]);
+    }
+
+    #[test]
+    fn commit_parsers_run_before_conventional_parsing() {
+        use crate::core::commit_parser::ParserRule;
+
+        let parsers = CommitParsers::new(&[
+            ParserRule {
+                pattern: "^:bug:".to_string(),
+                section: Some("Fixed".to_string()),
+                ..ParserRule::default()
+            },
+            ParserRule {
+                pattern: "^chore".to_string(),
+                skip: true,
+                ..ParserRule::default()
+            },
+            ParserRule {
+                pattern: r"^(?P<type>\w+)/(?P<description>.+)$".to_string(),
+                ..ParserRule::default()
+            },
+        ])
+        .expect("valid rules");
+        let commits = vec![
+            Commit::new("a", ":bug: fix crash"),
+            Commit::new("b", "chore: tidy"),
+            Commit::new("c", "feat/search box"),
+            Commit::new("d", "perf(io): buffer writes"),
+        ];
+
+        let notes = collect_notes(&commits, &BTreeMap::new(), &parsers);
+        let sections: Vec<(&str, &str)> = notes
+            .iter()
+            .map(|n| (n.section.as_str(), n.text.as_str()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Fixed", "fix crash"),
+                ("Added", "search box"),
+                ("Changed", "buffer writes")
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_labels_to_sections() {
+        let labels = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let mut mapping = BTreeMap::new();
+        mapping.insert("ux".to_string(), "Usability".to_string());
+
+        assert_eq!(
+            map_labels_to_section(&labels(&["triaged", "bug"]), &mapping).as_deref(),
+            Some("Fixed")
+        );
+        assert_eq!(
+            map_labels_to_section(&labels(&["enhancement", "ux"]), &mapping).as_deref(),
+            Some("Usability")
+        );
+        assert_eq!(map_labels_to_section(&labels(&["triaged"]), &mapping), None);
+    }
 }
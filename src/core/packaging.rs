@@ -0,0 +1,525 @@
+//! Distribution package changelogs: `debian/changelog`, RPM `%changelog`
+//! and AppStream `<releases>`.
+
+use crate::core::changelog::{
+    indent_width, ChangelogDocument, HeaderFormat, Release, ReleaseVersion,
+};
+use crate::core::html::escape;
+use chrono::{DateTime, NaiveDate};
+use pulldown_cmark::{Event, Parser};
+use regex::Regex;
+use semver::{BuildMetadata, Prerelease, Version};
+
+pub const DEFAULT_DISTRIBUTION: &str = "unstable";
+pub const DEFAULT_URGENCY: &str = "medium";
+pub const DEFAULT_REVISION: &str = "1";
+/// Section for imported notes that were not grouped under a heading.
+const DEFAULT_SECTION: &str = "Changed";
+/// Change line for releases without notes; both formats need at least one.
+const NO_CHANGES: &str = "New upstream release.";
+
+/// Package metadata the strict formats need on top of the changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packaging {
+    pub package: Option<String>,
+    /// `Full Name <email>`.
+    pub maintainer: Option<String>,
+    pub distribution: String,
+    pub urgency: String,
+    /// Debian revision / RPM release appended to the version.
+    pub revision: String,
+}
+
+impl Default for Packaging {
+    fn default() -> Self {
+        Self {
+            package: None,
+            maintainer: None,
+            distribution: DEFAULT_DISTRIBUTION.to_string(),
+            urgency: DEFAULT_URGENCY.to_string(),
+            revision: DEFAULT_REVISION.to_string(),
+        }
+    }
+}
+
+/// Export formats for distribution packaging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageFormat {
+    Debian,
+    Rpm,
+    AppStream,
+}
+
+impl PackageFormat {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "debian" | "deb" => Some(PackageFormat::Debian),
+            "rpm" => Some(PackageFormat::Rpm),
+            "appstream" | "flatpak" => Some(PackageFormat::AppStream),
+            _ => None,
+        }
+    }
+
+    /// Renders the released versions of `doc`; `Unreleased` is skipped.
+    pub fn render(self, doc: &ChangelogDocument, packaging: &Packaging) -> Result<String, String> {
+        match self {
+            PackageFormat::Debian => debian(doc, packaging),
+            PackageFormat::Rpm => rpm(doc, packaging),
+            PackageFormat::AppStream => Ok(appstream(doc)),
+        }
+    }
+}
+
+/// The version as Debian and RPM sort it: pre-releases after `~` so they
+/// sort before the final release, with `rc.1` shortened to `rc1`.
+pub fn upstream_version(version: &Version) -> String {
+    let mut out = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    if !version.pre.is_empty() {
+        out.push('~');
+        let mut previous_alpha = false;
+        for (index, ident) in version.pre.as_str().split('.').enumerate() {
+            let numeric = ident.chars().all(|c| c.is_ascii_digit());
+            if index > 0 && !(numeric && previous_alpha) {
+                out.push('.');
+            }
+            out.push_str(ident);
+            previous_alpha = !numeric;
+        }
+    }
+    if !version.build.is_empty() {
+        out.push('+');
+        out.push_str(version.build.as_str());
+    }
+    out
+}
+
+/// Reverses [`upstream_version`] for a Debian version, dropping the epoch
+/// and revision and padding missing components (`2.3` becomes `2.3.0`).
+pub fn parse_upstream_version(raw: &str) -> Option<Version> {
+    let raw = raw.split_once(':').map_or(raw, |(_, rest)| rest);
+    let raw = raw.rsplit_once('-').map_or(raw, |(upstream, _)| upstream);
+    let (raw, build) = raw.split_once('+').unwrap_or((raw, ""));
+    let (core, pre) = raw.split_once('~').unwrap_or((raw, ""));
+
+    let mut parts: Vec<u64> = Vec::new();
+    for part in core.split('.') {
+        parts.push(part.parse().ok()?);
+    }
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    parts.resize(3, 0);
+
+    let split = Regex::new(r"([A-Za-z]+)(\d+)").expect("valid regex");
+    let pre = split.replace_all(pre, "$1.$2");
+    Some(Version {
+        major: parts[0],
+        minor: parts[1],
+        patch: parts[2],
+        pre: Prerelease::new(&pre).ok()?,
+        build: BuildMetadata::new(build).ok()?,
+    })
+}
+
+fn debian(doc: &ChangelogDocument, packaging: &Packaging) -> Result<String, String> {
+    let package = packaging
+        .package
+        .as_deref()
+        .ok_or("debian/changelog needs a package name")?;
+    let maintainer = packaging
+        .maintainer
+        .as_deref()
+        .ok_or("debian/changelog needs a maintainer")?;
+
+    let mut out = String::new();
+    for (release, version, date) in dated_releases(doc)? {
+        out.push_str(&format!(
+            "{} ({}-{}) {}; urgency={}\n\n",
+            package,
+            upstream_version(version),
+            packaging.revision,
+            packaging.distribution,
+            packaging.urgency
+        ));
+        for (section, notes) in &release.sections {
+            out.push_str(&format!("  * {}:\n", section));
+            for note in notes {
+                out.push_str(&indent_note(note, "    - ", "    "));
+            }
+        }
+        if !has_notes(release) {
+            out.push_str(&format!("  * {}\n", NO_CHANGES));
+        }
+        out.push_str(&format!(
+            "\n -- {}  {}\n\n",
+            maintainer,
+            date.format("%a, %d %b %Y 00:00:00 +0000")
+        ));
+    }
+    Ok(out.trim_end().to_string() + "\n")
+}
+
+fn rpm(doc: &ChangelogDocument, packaging: &Packaging) -> Result<String, String> {
+    let maintainer = packaging
+        .maintainer
+        .as_deref()
+        .ok_or("RPM %changelog needs a maintainer")?;
+
+    let mut out = String::new();
+    for (release, version, date) in dated_releases(doc)? {
+        out.push_str(&format!(
+            "* {} {} - {}-{}\n",
+            date.format("%a %b %d %Y"),
+            maintainer,
+            upstream_version(version),
+            packaging.revision
+        ));
+        for (section, notes) in &release.sections {
+            for note in notes {
+                let prefix = format!("- {}: ", section);
+                out.push_str(&indent_note(note, &prefix, "  "));
+            }
+        }
+        if !has_notes(release) {
+            out.push_str(&format!("- {}\n", NO_CHANGES));
+        }
+        out.push('\n');
+    }
+    // rpmbuild expands macros in %changelog; `%%` is a literal percent sign.
+    Ok(out.trim_end().replace('%', "%%") + "\n")
+}
+
+fn has_notes(release: &Release) -> bool {
+    release.sections.values().any(|notes| !notes.is_empty())
+}
+
+fn appstream(doc: &ChangelogDocument) -> String {
+    let mut out = String::from("<releases>\n");
+    for release in &doc.releases {
+        let Some(version) = release.version.semver() else {
+            continue;
+        };
+        out.push_str(&format!(
+            "  <release version=\"{}\"",
+            escape(&upstream_version(version))
+        ));
+        if let Some(date) = release_date(release) {
+            out.push_str(&format!(" date=\"{}\"", date.format("%Y-%m-%d")));
+        }
+        if !version.pre.is_empty() {
+            out.push_str(" type=\"development\"");
+        }
+        out.push_str(">\n    <description>\n");
+        for (section, notes) in &release.sections {
+            out.push_str(&format!("      <p>{}</p>\n      <ul>\n", escape(section)));
+            for note in notes {
+                let text: Vec<String> = note.lines().map(plain_text).collect();
+                out.push_str(&format!("        <li>{}</li>\n", escape(&text.join(" "))));
+            }
+            out.push_str("      </ul>\n");
+        }
+        out.push_str("    </description>\n  </release>\n");
+    }
+    out.push_str("</releases>\n");
+    out
+}
+
+/// Parses a `debian/changelog`. `* Section:` bullets with `- note` items
+/// below them (as written by the exporter) keep their section; other
+/// bullets are filed under `Changed`.
+pub fn parse_debian(input: &str) -> Result<ChangelogDocument, String> {
+    let header = Regex::new(r"^(\S+) \(([^)]+)\) [^;]+;").expect("valid regex");
+    let section = Regex::new(r"^  \* (.+):$").expect("valid regex");
+    let trailer = Regex::new(r"^ -- .*?>  (.+)$").expect("valid regex");
+
+    let mut doc = ChangelogDocument::scaffold();
+    let mut current: Option<Release> = None;
+    let mut section_name: Option<String> = None;
+    let mut note: Option<(usize, Vec<String>)> = None;
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        if let Some(caps) = header.captures(line) {
+            let version = parse_upstream_version(&caps[2]).ok_or_else(|| {
+                format!(
+                    "line {}: cannot convert version '{}' to SemVer",
+                    line_number, &caps[2]
+                )
+            })?;
+            let mut release = Release::new(version);
+            release.date = None;
+            release.header = HeaderFormat::Default;
+            current = Some(release);
+            section_name = None;
+            continue;
+        }
+
+        let Some(release) = current.as_mut() else {
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Err(format!(
+                "line {}: expected an entry heading like 'package (1.0.0-1) unstable; urgency=medium'",
+                line_number
+            ));
+        };
+
+        if let Some(caps) = trailer.captures(line) {
+            flush_debian_note(release, &section_name, note.take());
+            let date = DateTime::parse_from_rfc2822(caps[1].trim())
+                .map_err(|e| format!("line {}: invalid date: {}", line_number, e))?;
+            release.date = Some(date.format("%Y-%m-%d").to_string());
+            doc.releases.extend(current.take());
+            continue;
+        }
+
+        if let Some(caps) = section.captures(line) {
+            flush_debian_note(release, &section_name, note.take());
+            section_name = Some(caps[1].to_string());
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let indent = indent_width(line);
+        if let Some(text) = trimmed.strip_prefix("* ").or_else(|| {
+            trimmed
+                .strip_prefix("- ")
+                .filter(|_| section_name.is_some())
+        }) {
+            let nested = note.as_ref().is_some_and(|(start, _)| indent > *start);
+            if !nested {
+                flush_debian_note(release, &section_name, note.take());
+                if trimmed.starts_with("* ") {
+                    section_name = None;
+                }
+                note = Some((indent, vec![text.trim().to_string()]));
+                continue;
+            }
+        }
+
+        match note.as_mut() {
+            _ if trimmed.is_empty() || trimmed.starts_with("[ ") => {}
+            Some((start, lines)) => {
+                let strip = indent.min(*start);
+                lines.push(line[strip..].trim_end().to_string());
+            }
+            None => {
+                note = Some((indent, vec![trimmed.trim_end().to_string()]));
+            }
+        }
+    }
+
+    if let Some(release) = current {
+        return Err(format!(
+            "entry {} has no ' -- maintainer  date' line",
+            release.version
+        ));
+    }
+    Ok(doc)
+}
+
+fn flush_debian_note(
+    release: &mut Release,
+    section: &Option<String>,
+    note: Option<(usize, Vec<String>)>,
+) {
+    if let Some((_, lines)) = note {
+        let section = section.as_deref().unwrap_or(DEFAULT_SECTION);
+        release
+            .sections
+            .entry(section.to_string())
+            .or_default()
+            .push(lines.join("\n"));
+    }
+}
+
+fn dated_releases(doc: &ChangelogDocument) -> Result<Vec<(&Release, &Version, NaiveDate)>, String> {
+    let mut out = Vec::new();
+    for release in &doc.releases {
+        let ReleaseVersion::Released(version) = &release.version else {
+            continue;
+        };
+        let date = release_date(release)
+            .ok_or_else(|| format!("release {} needs a YYYY-MM-DD date", version))?;
+        out.push((release, version, date));
+    }
+    Ok(out)
+}
+
+fn release_date(release: &Release) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(release.date.as_deref()?.trim(), "%Y-%m-%d").ok()
+}
+
+/// Writes a note as plain text: the first line after `first`, the rest
+/// (nested bullets, continuation lines) after `rest`.
+fn indent_note(note: &str, first: &str, rest: &str) -> String {
+    let mut out = String::new();
+    for (index, line) in note.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        let (bullet, text) = match trimmed.strip_prefix("- ") {
+            Some(text) if index > 0 => ("- ", text),
+            _ => ("", trimmed),
+        };
+        let prefix = if index == 0 { first } else { rest };
+        out.push_str(&format!(
+            "{}{}{}{}\n",
+            prefix,
+            indent,
+            bullet,
+            plain_text(text)
+        ));
+    }
+    out
+}
+
+/// Markdown inline markup reduced to its text: `**api:** [#12](url)`
+/// becomes `api: #12`.
+fn plain_text(markdown: &str) -> String {
+    let markdown = markdown.trim_start().trim_start_matches("- ");
+    let mut out = String::new();
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(text) | Event::Code(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                out.push_str(&text)
+            }
+            Event::SoftBreak | Event::HardBreak => out.push(' '),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packaging() -> Packaging {
+        Packaging {
+            package: Some("widgets".to_string()),
+            maintainer: Some("Jane Doe <jane@example.com>".to_string()),
+            ..Packaging::default()
+        }
+    }
+
+    fn document() -> ChangelogDocument {
+        ChangelogDocument::parse(
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n- wip\n\n## [1.1.0-rc.1] - 2026-01-02\n\n### Added\n- **api:** export ([#12](https://example.com/pull/12))\n  - nested `--flag`\n\n### Fixed\n- crash\n\n## [1.0.0] - 2025-12-01\n\n### Added\n- first\n",
+        )
+        .expect("parse changelog")
+    }
+
+    #[test]
+    fn mangles_versions() {
+        let version = |raw| Version::parse(raw).expect("version");
+        assert_eq!(upstream_version(&version("1.2.3")), "1.2.3");
+        assert_eq!(upstream_version(&version("2.0.0-rc.1")), "2.0.0~rc1");
+        assert_eq!(
+            upstream_version(&version("2.0.0-beta.2.x+build.5")),
+            "2.0.0~beta2.x+build.5"
+        );
+        assert_eq!(
+            parse_upstream_version("1:2.0.0~rc1-3"),
+            Some(version("2.0.0-rc.1"))
+        );
+        assert_eq!(parse_upstream_version("2.3-1"), Some(version("2.3.0")));
+        assert_eq!(parse_upstream_version("2.3a-1"), None);
+    }
+
+    #[test]
+    fn renders_debian_changelog_and_reads_it_back() {
+        let out = PackageFormat::Debian
+            .render(&document(), &packaging())
+            .expect("render debian");
+        assert_eq!(
+            out,
+            "widgets (1.1.0~rc1-1) unstable; urgency=medium\n\n  * Added:\n    - api: export (#12)\n      - nested --flag\n  * Fixed:\n    - crash\n\n -- Jane Doe <jane@example.com>  Fri, 02 Jan 2026 00:00:00 +0000\n\nwidgets (1.0.0-1) unstable; urgency=medium\n\n  * Added:\n    - first\n\n -- Jane Doe <jane@example.com>  Mon, 01 Dec 2025 00:00:00 +0000\n"
+        );
+
+        let imported = parse_debian(&out).expect("parse debian");
+        assert_eq!(imported.releases.len(), 2);
+        let release = &imported.releases[0];
+        assert_eq!(release.version.to_string(), "1.1.0-rc.1");
+        assert_eq!(release.date.as_deref(), Some("2026-01-02"));
+        assert_eq!(
+            release.sections["Added"],
+            vec!["api: export (#12)\n  - nested --flag"]
+        );
+        assert_eq!(release.sections["Fixed"], vec!["crash"]);
+    }
+
+    #[test]
+    fn imports_plain_debian_entries() {
+        let input = "widgets (2.3-1) unstable; urgency=low\n\n  [ Jane Doe ]\n  * New upstream release.\n  * Fix crash on\n    startup.\n\n -- Jane Doe <jane@example.com>  Tue, 04 Mar 2025 10:20:30 +0100\n";
+        let doc = parse_debian(input).expect("parse debian");
+        assert_eq!(doc.releases[0].version.to_string(), "2.3.0");
+        assert_eq!(
+            doc.releases[0].sections["Changed"],
+            vec!["New upstream release.", "Fix crash on\n  startup."]
+        );
+
+        let input = "widgets (2.3-1) unstable; urgency=low\n\n  * Fix crash on\n\u{3000}startup.\n\n -- Jane Doe <jane@example.com>  Tue, 04 Mar 2025 10:20:30 +0100\n";
+        let doc = parse_debian(input).expect("parse debian");
+        assert_eq!(
+            doc.releases[0].sections["Changed"],
+            vec!["Fix crash on\n\u{3000}startup."]
+        );
+    }
+
+    #[test]
+    fn renders_rpm_and_appstream() {
+        let rpm = PackageFormat::Rpm
+            .render(&document(), &packaging())
+            .expect("render rpm");
+        assert!(rpm.starts_with(
+            "* Fri Jan 02 2026 Jane Doe <jane@example.com> - 1.1.0~rc1-1\n- Added: api: export (#12)\n    - nested --flag\n- Fixed: crash\n\n* Mon Dec 01 2025"
+        ));
+
+        let appstream = PackageFormat::AppStream
+            .render(&document(), &Packaging::default())
+            .expect("render appstream");
+        assert!(appstream.contains(
+            "  <release version=\"1.1.0~rc1\" date=\"2026-01-02\" type=\"development\">\n    <description>\n      <p>Added</p>\n      <ul>\n        <li>api: export (#12) nested --flag</li>\n"
+        ));
+        assert!(!appstream.contains("wip"));
+    }
+
+    #[test]
+    fn escapes_rpm_macros_and_fills_empty_releases() {
+        let doc = ChangelogDocument::parse(
+            "# Changelog\n\n## [1.1.0] - 2026-01-02\n\n### Changed\n- 100% faster `%{_bindir}` lookups\n\n## [1.0.0] - 2025-12-01\n",
+        )
+        .expect("parse changelog");
+
+        let rpm = PackageFormat::Rpm
+            .render(&doc, &packaging())
+            .expect("render rpm");
+        assert_eq!(
+            rpm,
+            "* Fri Jan 02 2026 Jane Doe <jane@example.com> - 1.1.0-1\n- Changed: 100%% faster %%{_bindir} lookups\n\n* Mon Dec 01 2025 Jane Doe <jane@example.com> - 1.0.0-1\n- New upstream release.\n"
+        );
+
+        let debian = PackageFormat::Debian
+            .render(&doc, &packaging())
+            .expect("render debian");
+        assert!(debian.contains(
+            "widgets (1.0.0-1) unstable; urgency=medium\n\n  * New upstream release.\n\n -- Jane Doe"
+        ));
+        assert!(debian.contains("100% faster"));
+    }
+
+    #[test]
+    fn requires_maintainer_and_dates() {
+        let err = PackageFormat::Rpm
+            .render(&document(), &Packaging::default())
+            .expect_err("no maintainer");
+        assert!(err.contains("maintainer"));
+
+        let mut doc = document();
+        doc.releases[2].date = None;
+        let err = PackageFormat::Debian
+            .render(&doc, &packaging())
+            .expect_err("no date");
+        assert!(err.contains("1.0.0 needs a YYYY-MM-DD date"));
+    }
+}
@@ -0,0 +1,106 @@
+//! `[[commit_preprocessors]]` and `[[note_postprocessors]]`: regex
+//! replacements applied to commit messages before they are parsed and to
+//! note text once notes are collected.
+
+use regex::Regex;
+use serde::Deserialize;
+
+/// One replacement as written in the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct TextProcessor {
+    pub pattern: String,
+    /// Replacement for every match; `$1` / `$name` expand captures. Empty
+    /// by default, which deletes the match.
+    #[serde(default)]
+    pub replace: String,
+}
+
+/// Replacements compiled in config order, each applied to the output of
+/// the previous one.
+#[derive(Debug, Clone, Default)]
+pub struct TextProcessors {
+    rules: Vec<(Regex, String)>,
+}
+
+impl TextProcessors {
+    /// `kind` names the config table in error messages.
+    pub fn new(processors: &[TextProcessor], kind: &str) -> Result<Self, String> {
+        let rules = processors
+            .iter()
+            .enumerate()
+            .map(|(index, processor)| {
+                Regex::new(&processor.pattern)
+                    .map(|regex| (regex, processor.replace.clone()))
+                    .map_err(|e| format!("{} {}: invalid pattern: {}", kind, index + 1, e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.rules
+            .iter()
+            .fold(text.to_string(), |text, (regex, replace)| {
+                regex.replace_all(&text, replace.as_str()).into_owned()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor(pattern: &str, replace: &str) -> TextProcessor {
+        TextProcessor {
+            pattern: pattern.to_string(),
+            replace: replace.to_string(),
+        }
+    }
+
+    #[test]
+    fn applies_replacements_in_order() {
+        let processors = TextProcessors::new(
+            &[
+                processor(
+                    r"\b([A-Z]+-\d+)\b",
+                    "[$1](https://jira.example.com/browse/$1)",
+                ),
+                processor(r"\B@([\w-]+)", "[@$1](https://github.com/$1)"),
+                processor(
+                    r"(^|\s)#(\d+)\b",
+                    "$1[#$2](https://github.com/acme/widgets/issues/$2)",
+                ),
+            ],
+            "note postprocessor",
+        )
+        .expect("valid processors");
+
+        assert_eq!(
+            processors.apply("fix WEB-12 and #42, thanks @jane-doe"),
+            "fix [WEB-12](https://jira.example.com/browse/WEB-12) and \
+             [#42](https://github.com/acme/widgets/issues/42), \
+             thanks [@jane-doe](https://github.com/jane-doe)"
+        );
+    }
+
+    #[test]
+    fn empty_replacement_deletes_matches() {
+        let processors = TextProcessors::new(
+            &[processor(r"^Merge pull request #\d+ from \S+\s*", "")],
+            "commit preprocessor",
+        )
+        .expect("valid processors");
+        assert_eq!(
+            processors.apply("Merge pull request #7 from acme/topic\n\nfeat: add export"),
+            "feat: add export"
+        );
+
+        let error = TextProcessors::new(&[processor("(", "")], "commit preprocessor")
+            .expect_err("invalid regex");
+        assert!(error.starts_with("commit preprocessor 1: invalid pattern"));
+    }
+}
@@ -1,5 +1,242 @@
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+use crate::core::notes::parse_conventional_header;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Commit {
+    /// First line of the commit message.
     pub message: String,
     pub hash: String,
+    /// Message text after the summary line, including any trailers.
+    pub body: String,
+    pub trailers: Vec<Trailer>,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub author_name: String,
+    pub author_email: String,
+    /// Author and committer times as Unix timestamps.
+    pub authored_at: i64,
+    pub committed_at: i64,
+    pub parent_count: usize,
+}
+
+/// A `Key: value` (or `Key #value`) line from the last paragraph of a
+/// commit message, e.g. `BREAKING CHANGE: ...` or `Refs: #42`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Trailer {
+    pub key: String,
+    pub value: String,
+}
+
+impl Commit {
+    /// Builds a commit from its hash and raw message, splitting off the body
+    /// and trailers and reading scope and breaking marker from the header.
+    pub fn new(hash: impl Into<String>, raw_message: &str) -> Self {
+        let raw_message = raw_message.trim();
+        let (summary, body) = match raw_message.split_once('\n') {
+            Some((summary, body)) => (summary.trim(), body.trim()),
+            None => (raw_message, ""),
+        };
+
+        let trailers = parse_trailers(body);
+        let header = parse_conventional_header(summary);
+        let breaking = header.as_ref().is_some_and(|h| h.breaking)
+            || trailers.iter().any(Trailer::is_breaking_change);
+
+        Self {
+            message: summary.to_string(),
+            hash: hash.into(),
+            body: body.to_string(),
+            trailers,
+            scope: header.and_then(|h| h.scope),
+            breaking,
+            ..Self::default()
+        }
+    }
+
+    /// Text of the `BREAKING CHANGE:` footer, if the commit has one.
+    pub fn breaking_description(&self) -> Option<&str> {
+        self.trailers
+            .iter()
+            .find(|trailer| trailer.is_breaking_change())
+            .map(|trailer| trailer.value.as_str())
+    }
+
+    /// Summary and body joined back into the full message.
+    pub fn raw_message(&self) -> String {
+        match self.body.is_empty() {
+            true => self.message.clone(),
+            false => format!("{}\n\n{}", self.message, self.body),
+        }
+    }
+
+    /// The commit re-read from a rewritten message, keeping hash, author,
+    /// dates and parents.
+    pub fn with_message(self, raw_message: &str) -> Self {
+        Self {
+            author_name: self.author_name,
+            author_email: self.author_email,
+            authored_at: self.authored_at,
+            committed_at: self.committed_at,
+            parent_count: self.parent_count,
+            ..Self::new(self.hash, raw_message)
+        }
+    }
+
+    pub fn is_merge(&self) -> bool {
+        self.parent_count > 1
+    }
+
+    /// Pull request number from a GitHub merge commit (`Merge pull request
+    /// #12 from ...`), another merge commit referencing `#12` in its summary
+    /// (e.g. bors' `Merge #12`) or a squash-merge summary ending in `(#12)`.
+    pub fn pull_request_number(&self) -> Option<u64> {
+        if self.is_merge() || self.message.starts_with("Merge pull request #") {
+            let number = self.message.split('#').skip(1).find_map(|rest| {
+                let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+                digits.parse().ok()
+            });
+            if number.is_some() {
+                return number;
+            }
+        }
+
+        let inner = self.message.trim_end().strip_suffix(')')?;
+        let (_, number) = inner.rsplit_once("(#")?;
+        number.parse().ok()
+    }
+
+    /// Summary line without a trailing `(#12)` pull request reference.
+    pub fn summary_without_pr_reference(&self) -> &str {
+        let summary = self.message.trim_end();
+        match summary.rsplit_once(" (#") {
+            Some((head, tail))
+                if tail
+                    .strip_suffix(')')
+                    .is_some_and(|n| n.parse::<u64>().is_ok()) =>
+            {
+                head.trim_end()
+            }
+            _ => summary,
+        }
+    }
+}
+
+impl Trailer {
+    pub fn is_breaking_change(&self) -> bool {
+        self.key == "BREAKING CHANGE" || self.key == "BREAKING-CHANGE"
+    }
+}
+
+/// Parses trailers from the last paragraph of a message body. Indented
+/// lines continue the previous trailer. Returns nothing when any line of the
+/// paragraph is not a trailer.
+pub fn parse_trailers(body: &str) -> Vec<Trailer> {
+    let Some(paragraph) = body.trim_end().rsplit("\n\n").next() else {
+        return Vec::new();
+    };
+
+    let mut trailers: Vec<Trailer> = Vec::new();
+    for line in paragraph.lines() {
+        if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            match trailers.last_mut() {
+                Some(last) => {
+                    last.value.push(' ');
+                    last.value.push_str(line.trim());
+                    continue;
+                }
+                None => return Vec::new(),
+            }
+        }
+
+        match parse_trailer_line(line.trim()) {
+            Some(trailer) => trailers.push(trailer),
+            None => return Vec::new(),
+        }
+    }
+
+    trailers
+}
+
+fn parse_trailer_line(line: &str) -> Option<Trailer> {
+    let (key, value) = if let Some(value) = line.strip_prefix("BREAKING CHANGE:") {
+        ("BREAKING CHANGE", value)
+    } else if let Some((key, value)) = line.split_once(": ") {
+        (key, value)
+    } else {
+        let (key, value) = line.split_once(" #")?;
+        return is_trailer_key(key).then(|| Trailer {
+            key: key.to_string(),
+            value: format!("#{}", value.trim()),
+        });
+    };
+
+    if !is_trailer_key(key) || value.trim().is_empty() {
+        return None;
+    }
+
+    Some(Trailer {
+        key: key.to_string(),
+        value: value.trim().to_string(),
+    })
+}
+
+fn is_trailer_key(key: &str) -> bool {
+    key == "BREAKING CHANGE"
+        || (!key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_body_and_trailers() {
+        let commit = Commit::new(
+            "abc",
+            "feat(api)!: drop v1 routes\n\nThe old routes are gone.\n\nBREAKING CHANGE: clients must\n  call /v2 instead\nRefs #42\nReviewed-by: Alice",
+        );
+
+        assert_eq!(commit.message, "feat(api)!: drop v1 routes");
+        assert_eq!(commit.scope.as_deref(), Some("api"));
+        assert!(commit.breaking);
+        assert_eq!(
+            commit.breaking_description(),
+            Some("clients must call /v2 instead")
+        );
+        assert_eq!(commit.trailers.len(), 3);
+        assert_eq!(commit.trailers[1].value, "#42");
+        assert!(commit.body.starts_with("The old routes are gone."));
+    }
+
+    #[test]
+    fn finds_pull_request_numbers() {
+        let squash = Commit::new("a", "feat: add export (#123)");
+        assert_eq!(squash.pull_request_number(), Some(123));
+        assert_eq!(squash.summary_without_pr_reference(), "feat: add export");
+
+        let merge = Commit::new("b", "Merge pull request #45 from alice/topic\n\nAdd topic");
+        assert_eq!(merge.pull_request_number(), Some(45));
+
+        let bors = Commit {
+            parent_count: 2,
+            ..Commit::new("d", "Merge #46: speed up parsing")
+        };
+        assert!(bors.is_merge());
+        assert_eq!(bors.pull_request_number(), Some(46));
+        let linear = Commit::new("e", "Merge #46: speed up parsing");
+        assert_eq!(linear.pull_request_number(), None);
+
+        let plain = Commit::new("c", "fix: handle (#) edge case");
+        assert_eq!(plain.pull_request_number(), None);
+        assert_eq!(
+            plain.summary_without_pr_reference(),
+            "fix: handle (#) edge case"
+        );
+    }
+
+    #[test]
+    fn prose_paragraph_is_not_a_trailer_block() {
+        let commit = Commit::new("abc", "fix: x\n\nThis fixes: a crash on startup");
+        assert!(commit.trailers.is_empty());
+        assert!(!commit.breaking);
+    }
 }
@@ -1,3 +1,2 @@
-pub mod changelog;
 pub mod commit;
 pub mod version;
@@ -1,3 +1,4 @@
+use crate::core::git::to_domain_commit;
 use crate::domain::commit::Commit;
 use crate::traits::git::GitRepository;
 use anyhow::Result;
@@ -17,10 +18,7 @@ impl GitRepository for Git2Repository {
         for oid in revwalk {
             let oid = oid?;
             let commit = repo.find_commit(oid)?;
-            commits.push(Commit {
-                message: commit.message().unwrap_or("").into(),
-                hash: oid.to_string(),
-            });
+            commits.push(to_domain_commit(&commit));
         }
 
         Ok(commits)
@@ -1,30 +1,434 @@
-use anyhow::Result;
-use reqwest::Client;
-use serde::Deserialize;
+use crate::core::forge::{Forge, ForgeKind, ForgeRelease};
+use anyhow::{anyhow, Result};
+use reqwest::header::{ACCEPT, AUTHORIZATION, LINK, USER_AGENT};
+use reqwest::{Client, Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-#[derive(Deserialize)]
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PullRequest {
+    pub number: u64,
     pub title: String,
     pub html_url: String,
+    pub user: Option<User>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+}
+
+/// A GitHub Release as returned by the releases API.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct GitHubRelease {
+    pub id: u64,
+    pub html_url: String,
+    #[serde(default)]
+    pub tag_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
 }
 
-pub async fn fetch_pr(repo: &str, number: u32) -> Result<PullRequest> {
-    let client = Client::new();
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Label {
+    pub name: String,
+}
 
-    let url = format!("https://api.github.com/repos/{}/pulls/{}", repo, number);
+/// An issue or pull request as returned by the issues API.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub pull_request: Option<PullRequestRef>,
+}
 
-    let res = client
-        .get(&url)
-        .header("User-Agent", "changeloggen-cli")
-        .send()
-        .await?
-        .json::<PullRequest>()
-        .await?;
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct PullRequestRef {
+    pub merged_at: Option<String>,
+}
 
-    Ok(res)
+impl PullRequest {
+    pub fn label_names(&self) -> Vec<String> {
+        self.labels.iter().map(|label| label.name.clone()).collect()
+    }
 }
 
-pub fn generate_compare_link() -> Result<String> {
-    // TODO: Implement GitHub compare link generation
-    Ok(String::new())
+impl Issue {
+    pub fn label_names(&self) -> Vec<String> {
+        self.labels.iter().map(|label| label.name.clone()).collect()
+    }
+
+    /// Closed pull requests that were not merged did not ship anything.
+    pub fn is_unmerged_pull_request(&self) -> bool {
+        self.pull_request
+            .as_ref()
+            .is_some_and(|pr| pr.merged_at.is_none())
+    }
+}
+
+/// GitHub REST client. `api_url` is `https://api.github.com` or the API root
+/// of a GitHub Enterprise (or mock) server.
+pub struct GitHubClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GitHubClient {
+    pub fn new(api_url: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token: token.filter(|token| !token.is_empty()),
+        }
+    }
+
+    /// Looks up a milestone by number or by title (open or closed).
+    pub async fn find_milestone(&self, repo: &str, milestone: &str) -> Result<Milestone> {
+        if let Ok(number) = milestone.parse::<u64>() {
+            let url = format!("{}/repos/{}/milestones/{}", self.api_url, repo, number);
+            return self.get(&url).await.map(|(milestone, _)| milestone);
+        }
+
+        let url = format!(
+            "{}/repos/{}/milestones?state=all&per_page=100",
+            self.api_url, repo
+        );
+        let milestones: Vec<Milestone> = self.get_all(url).await?;
+        milestones
+            .into_iter()
+            .find(|m| m.title == milestone)
+            .ok_or_else(|| anyhow!("milestone '{}' not found in {}", milestone, repo))
+    }
+
+    /// Closed issues and merged pull requests of a milestone, oldest first.
+    pub async fn milestone_items(&self, repo: &str, milestone: u64) -> Result<Vec<Issue>> {
+        let url = format!(
+            "{}/repos/{}/issues?milestone={}&state=closed&sort=created&direction=asc&per_page=100",
+            self.api_url, repo, milestone
+        );
+        let issues: Vec<Issue> = self.get_all(url).await?;
+        Ok(issues
+            .into_iter()
+            .filter(|issue| !issue.is_unmerged_pull_request())
+            .collect())
+    }
+
+    pub async fn fetch_pr(&self, repo: &str, number: u64) -> Result<PullRequest> {
+        let url = format!("{}/repos/{}/pulls/{}", self.api_url, repo, number);
+        self.get(&url).await.map(|(pr, _)| pr)
+    }
+
+    /// Creates the release for `release.tag`, or updates it when one exists.
+    /// Returns the release and whether it was created. Releases are found
+    /// by listing them, since the by-tag endpoint does not return drafts.
+    pub async fn publish_release(
+        &self,
+        repo: &str,
+        release: &ForgeRelease,
+    ) -> Result<(GitHubRelease, bool)> {
+        let list = format!("{}/repos/{}/releases?per_page=100", self.api_url, repo);
+        let payload = serde_json::json!({
+            "tag_name": release.tag,
+            "name": release.name,
+            "body": release.body,
+            "draft": release.draft,
+            "prerelease": release.prerelease,
+        });
+
+        let releases: Vec<GitHubRelease> = self.get_all(list).await?;
+        match releases.into_iter().find(|r| r.tag_name == release.tag) {
+            Some(existing) => {
+                let url = format!("{}/repos/{}/releases/{}", self.api_url, repo, existing.id);
+                let updated = self.send(Method::PATCH, &url, &payload).await?;
+                Ok((updated, false))
+            }
+            None => {
+                let url = format!("{}/repos/{}/releases", self.api_url, repo);
+                let created = self.send(Method::POST, &url, &payload).await?;
+                Ok((created, true))
+            }
+        }
+    }
+
+    /// Fetches every page of a list endpoint by following `Link: rel="next"`.
+    async fn get_all<T: DeserializeOwned>(&self, url: String) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut next = Some(url);
+        while let Some(url) = next {
+            let (page, link): (Vec<T>, _) = self.get(&url).await?;
+            items.extend(page);
+            next = link.as_deref().and_then(next_page_url);
+        }
+        Ok(items)
+    }
+
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, url)
+            .header(USER_AGENT, "changeloggen-cli")
+            .header(ACCEPT, "application/vnd.github+json");
+        match &self.token {
+            Some(token) => request.header(AUTHORIZATION, format!("Bearer {}", token)),
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        payload: &serde_json::Value,
+    ) -> Result<T> {
+        let response = self
+            .request(method.clone(), url)
+            .json(payload)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "{} {} returned {}: {}",
+                method,
+                url,
+                status,
+                body.trim()
+            ));
+        }
+        Ok(response.json::<T>().await?)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: &str) -> Result<(T, Option<String>)> {
+        let response = self.request(Method::GET, url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("GET {} returned {}: {}", url, status, body.trim()));
+        }
+
+        let link = response
+            .headers()
+            .get(LINK)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        Ok((response.json::<T>().await?, link))
+    }
+}
+
+/// URL of the `rel="next"` entry of a `Link` header.
+pub fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|entry| {
+        let (url, params) = entry.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// `owner/repo` of a GitHub remote URL.
+pub fn repository_slug(remote: &str) -> Option<String> {
+    let forge = Forge::from_remote(remote, Some(ForgeKind::GitHub))?;
+    Some(forge.project_path().to_string())
+}
+
+/// Pull requests fetched earlier, stored as `<dir>/<owner>/<repo>/<number>.json`
+/// so repeated runs do not query the API again.
+pub struct PullRequestCache {
+    dir: PathBuf,
+}
+
+impl PullRequestCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$CHANGELOGGEN_CACHE_DIR`, else the user cache directory.
+    pub fn default_dir() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os("CHANGELOGGEN_CACHE_DIR") {
+            return Some(PathBuf::from(dir));
+        }
+        directories::ProjectDirs::from("", "", "changeloggen-cli")
+            .map(|dirs| dirs.cache_dir().join("github"))
+    }
+
+    pub fn get(&self, repo: &str, number: u64) -> Option<PullRequest> {
+        let content = fs::read_to_string(self.path(repo, number)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn put(&self, repo: &str, pr: &PullRequest) -> Result<()> {
+        let path = self.path(repo, pr.number);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(pr)?)?;
+        Ok(())
+    }
+
+    fn path(&self, repo: &str, number: u64) -> PathBuf {
+        self.dir
+            .join(Path::new(repo))
+            .join(format!("{}.json", number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_next_page_from_link_header() {
+        let link = "<https://api.github.com/repositories/1/issues?page=2>; rel=\"next\", \
+                    <https://api.github.com/repositories/1/issues?page=5>; rel=\"last\"";
+        assert_eq!(
+            next_page_url(link).as_deref(),
+            Some("https://api.github.com/repositories/1/issues?page=2")
+        );
+        assert_eq!(
+            next_page_url("<https://x/issues?page=1>; rel=\"prev\""),
+            None
+        );
+        assert_eq!(
+            repository_slug("git@github.com:acme/widgets.git").as_deref(),
+            Some("acme/widgets")
+        );
+    }
+
+    #[test]
+    fn caches_pull_requests_on_disk() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = PullRequestCache::new(dir.path());
+        let pr = PullRequest {
+            number: 12,
+            title: "Add export".to_string(),
+            html_url: "https://github.com/acme/widgets/pull/12".to_string(),
+            user: Some(User {
+                login: "alice".to_string(),
+            }),
+            labels: Vec::new(),
+        };
+
+        assert_eq!(cache.get("acme/widgets", 12), None);
+        cache.put("acme/widgets", &pr).expect("cache write");
+        assert_eq!(cache.get("acme/widgets", 12), Some(pr));
+        assert!(dir.path().join("acme/widgets/12.json").is_file());
+    }
+
+    #[tokio::test]
+    async fn lists_milestone_items_across_pages() {
+        let mut server = mockito::Server::new_async().await;
+        let next = format!("<{}/page2>; rel=\"next\"", server.url());
+        let first = server
+            .mock(
+                "GET",
+                "/repos/acme/widgets/milestones?state=all&per_page=100",
+            )
+            .with_body(r#"[{"number": 7, "title": "v1.0"}]"#)
+            .create_async()
+            .await;
+        let page1 = server
+            .mock("GET", mockito::Matcher::Regex("milestone=7".to_string()))
+            .match_header("authorization", "Bearer secret")
+            .with_header("link", &next)
+            .with_body(
+                r#"[{"number": 1, "title": "Crash on start", "html_url": "u1",
+                     "labels": [{"name": "bug"}]},
+                    {"number": 2, "title": "Abandoned", "html_url": "u2",
+                     "pull_request": {"merged_at": null}}]"#,
+            )
+            .create_async()
+            .await;
+        let page2 = server
+            .mock("GET", "/page2")
+            .with_body(
+                r#"[{"number": 3, "title": "Add export", "html_url": "u3",
+                     "pull_request": {"merged_at": "2024-01-01T00:00:00Z"}}]"#,
+            )
+            .create_async()
+            .await;
+
+        let client = GitHubClient::new(server.url(), Some("secret".to_string()));
+        let milestone = client
+            .find_milestone("acme/widgets", "v1.0")
+            .await
+            .expect("milestone");
+        let items = client
+            .milestone_items("acme/widgets", milestone.number)
+            .await
+            .expect("items");
+
+        let numbers: Vec<u64> = items.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![1, 3]);
+        assert_eq!(items[0].label_names(), vec!["bug".to_string()]);
+        first.assert_async().await;
+        page1.assert_async().await;
+        page2.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn updates_an_existing_draft_release() {
+        let mut server = mockito::Server::new_async().await;
+        let list = server
+            .mock("GET", "/repos/acme/widgets/releases?per_page=100")
+            .with_body(
+                r#"[{"id": 3, "html_url": "u3", "tag_name": "v0.9.0"},
+                    {"id": 4, "html_url": "u4", "tag_name": "v1.0.0", "draft": true}]"#,
+            )
+            .create_async()
+            .await;
+        let update = server
+            .mock("PATCH", "/repos/acme/widgets/releases/4")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "tag_name": "v1.0.0",
+                "draft": true,
+            })))
+            .with_body(r#"{"id": 4, "html_url": "u4", "tag_name": "v1.0.0"}"#)
+            .create_async()
+            .await;
+        let create = server
+            .mock("POST", "/repos/acme/widgets/releases")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = GitHubClient::new(server.url(), None);
+        let release = ForgeRelease {
+            tag: "v1.0.0".to_string(),
+            name: "v1.0.0".to_string(),
+            body: "### Fixed\n- crash".to_string(),
+            draft: true,
+            prerelease: false,
+        };
+        let (published, created) = client
+            .publish_release("acme/widgets", &release)
+            .await
+            .expect("publish");
+
+        assert_eq!(published.id, 4);
+        assert!(!created);
+        list.assert_async().await;
+        update.assert_async().await;
+        create.assert_async().await;
+    }
 }
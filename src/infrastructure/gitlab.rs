@@ -0,0 +1,152 @@
+use crate::core::forge::ForgeRelease;
+use anyhow::{anyhow, Result};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use serde::Deserialize;
+
+/// A GitLab release as returned by the releases API.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct GitLabRelease {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// GitLab REST client for `api_url` (e.g. `https://gitlab.com/api/v4`).
+pub struct GitLabClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GitLabClient {
+    pub fn new(api_url: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token: token.filter(|token| !token.is_empty()),
+        }
+    }
+
+    /// Creates the release for `release.tag` in `project` (`group/repo`), or
+    /// updates it when one exists. Returns whether it was created.
+    pub async fn publish_release(&self, project: &str, release: &ForgeRelease) -> Result<bool> {
+        if release.draft || release.prerelease {
+            return Err(anyhow!(
+                "GitLab releases have no draft or pre-release state"
+            ));
+        }
+
+        let base = format!(
+            "{}/projects/{}/releases",
+            self.api_url,
+            encode_segment(project)
+        );
+        let existing = format!("{}/{}", base, encode_segment(&release.tag));
+        let payload = serde_json::json!({
+            "tag_name": release.tag,
+            "name": release.name,
+            "description": release.body,
+        });
+
+        let response = self.request(Method::GET, &existing).send().await?;
+        let (method, url, created) = match response.status() {
+            StatusCode::NOT_FOUND => (Method::POST, base, true),
+            status if status.is_success() => (Method::PUT, existing, false),
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow!(
+                    "GET {} returned {}: {}",
+                    existing,
+                    status,
+                    body.trim()
+                ));
+            }
+        };
+
+        let response = self
+            .request(method.clone(), &url)
+            .json(&payload)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "{} {} returned {}: {}",
+                method,
+                url,
+                status,
+                body.trim()
+            ));
+        }
+        response.json::<GitLabRelease>().await?;
+        Ok(created)
+    }
+
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, url)
+            .header("User-Agent", "changeloggen-cli");
+        match &self.token {
+            Some(token) => request.header("PRIVATE-TOKEN", token),
+            None => request,
+        }
+    }
+}
+
+/// Percent-encodes a path or tag for use as a single URL path segment.
+fn encode_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn creates_missing_release() {
+        let mut server = mockito::Server::new_async().await;
+        let lookup = server
+            .mock("GET", "/projects/group%2Fsub%2Frepo/releases/v1.0.0")
+            .with_status(404)
+            .create_async()
+            .await;
+        let create = server
+            .mock("POST", "/projects/group%2Fsub%2Frepo/releases")
+            .match_header("private-token", "secret")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "tag_name": "v1.0.0",
+                "description": "### Added\n- a",
+            })))
+            .with_status(201)
+            .with_body(r#"{"tag_name": "v1.0.0", "name": "v1.0.0"}"#)
+            .create_async()
+            .await;
+
+        let client = GitLabClient::new(server.url(), Some("secret".to_string()));
+        let release = ForgeRelease {
+            tag: "v1.0.0".to_string(),
+            name: "v1.0.0".to_string(),
+            body: "### Added\n- a".to_string(),
+            draft: false,
+            prerelease: false,
+        };
+        let created = client
+            .publish_release("group/sub/repo", &release)
+            .await
+            .expect("publish");
+
+        assert!(created);
+        lookup.assert_async().await;
+        create.assert_async().await;
+    }
+}
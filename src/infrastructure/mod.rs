@@ -1,6 +1,7 @@
 pub mod git;
 pub mod github;
+pub mod gitlab;
 pub mod plugins;
-pub mod template;
 pub mod templates;
+pub mod version_files;
 pub mod workspace;
@@ -1,16 +1,499 @@
-use anyhow::Result;
-use handlebars::Handlebars;
+//! Named Handlebars templates: the built-ins, `.hbs` files from the user
+//! template directory and from the directory of a `--template` file.
+
+use crate::core::commit_parser::CommitParsers;
+use crate::core::context::{short_hash, TemplateContext};
+use crate::core::forge::{Forge, ForgeKind};
+use crate::core::notes::collect_notes;
+use crate::domain::commit::Commit;
+use crate::traits::template::TemplateRenderer;
+use anyhow::{anyhow, Context as _, Result};
+use chrono::{DateTime, NaiveDate};
+use handlebars::{
+    handlebars_helper, Context, Handlebars, Helper, HelperResult, Output, RenderContext,
+    RenderErrorReason,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Template used when none is named.
+pub const DEFAULT_TEMPLATE: &str = "keepachangelog";
+
+/// Name, description and source of each built-in template.
+const BUILTIN: [(&str, &str, &str); 7] = [
+    (
+        "keepachangelog",
+        "Keep a Changelog sections, like the built-in generate output",
+        include_str!("../../templates/keepachangelog.hbs"),
+    ),
+    (
+        "github-release",
+        "GitHub release body with commit links, contributors and a compare link",
+        include_str!("../../templates/github-release.hbs"),
+    ),
+    (
+        "compact",
+        "one line per section",
+        include_str!("../../templates/compact.hbs"),
+    ),
+    (
+        "slack",
+        "Slack mrkdwn message",
+        include_str!("../../templates/slack.hbs"),
+    ),
+    (
+        "document",
+        "whole changelog for release and show, like the built-in layout",
+        include_str!("../../templates/document.hbs"),
+    ),
+    (
+        "release",
+        "one release of a changelog, for `{{> release}}`",
+        include_str!("../../templates/release.hbs"),
+    ),
+    (
+        "note",
+        "one note with its scope and pull request link, for `{{> note}}`",
+        include_str!("../../templates/note.hbs"),
+    ),
+];
+
+/// Where a registered template was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateOrigin {
+    BuiltIn(&'static str),
+    File(PathBuf),
+}
+
+/// Handlebars registry with the helpers from [`register_helpers`]. Every
+/// template is also registered as a partial under its name, so
+/// `{{> note}}` works in any template.
+pub struct TemplateRegistry {
+    handlebars: Handlebars<'static>,
+    templates: BTreeMap<String, (TemplateOrigin, String)>,
+}
+
+impl TemplateRegistry {
+    /// The built-in templates only.
+    pub fn builtin() -> Self {
+        let mut handlebars = Handlebars::new();
+        // Output is Markdown, not HTML.
+        handlebars.register_escape_fn(handlebars::no_escape);
+        register_helpers(&mut handlebars);
+
+        let mut registry = Self {
+            handlebars,
+            templates: BTreeMap::new(),
+        };
+        for (name, description, source) in BUILTIN {
+            registry
+                .register(
+                    name,
+                    TemplateOrigin::BuiltIn(description),
+                    source.to_string(),
+                )
+                .expect("built-in templates parse");
+        }
+        registry
+    }
+
+    /// Built-ins, overridden and extended by the user template directory.
+    pub fn load() -> Result<Self> {
+        let mut registry = Self::builtin();
+        if let Some(dir) = user_dir().filter(|dir| dir.is_dir()) {
+            registry.add_dir(&dir)?;
+        }
+        Ok(registry)
+    }
+
+    /// Registers every `.hbs` file in `dir` under its file stem.
+    pub fn add_dir(&mut self, dir: &Path) -> Result<()> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .with_context(|| format!("cannot read template directory {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "hbs"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            self.add_file(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Registers `path` under its file stem and returns the name.
+    pub fn add_file(&mut self, path: &Path) -> Result<String> {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| anyhow!("invalid template file name {}", path.display()))?
+            .to_string();
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("cannot read template {}", path.display()))?;
+        self.register(&name, TemplateOrigin::File(path.to_path_buf()), source)
+            .with_context(|| format!("invalid template {}", path.display()))?;
+        Ok(name)
+    }
+
+    /// Name of the template for `--template`: an existing file is
+    /// registered together with the other `.hbs` files next to it, which
+    /// its partials may refer to; anything else must be a registered name.
+    pub fn resolve(&mut self, template: &Path) -> Result<String> {
+        if template.is_file() {
+            let dir = match template.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            self.add_dir(dir)?;
+            return self.add_file(template);
+        }
+
+        let name = template.to_string_lossy();
+        if self.templates.contains_key(name.as_ref()) {
+            return Ok(name.into_owned());
+        }
+        Err(anyhow!(
+            "unknown template '{}'; use a .hbs file or one of: {}",
+            name,
+            self.names().collect::<Vec<_>>().join(", ")
+        ))
+    }
+
+    pub fn render(&self, name: &str, data: &impl Serialize) -> Result<String> {
+        Ok(self.handlebars.render(name, data)?)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    pub fn origin(&self, name: &str) -> Option<&TemplateOrigin> {
+        self.templates.get(name).map(|(origin, _)| origin)
+    }
+
+    pub fn source(&self, name: &str) -> Option<&str> {
+        self.templates.get(name).map(|(_, source)| source.as_str())
+    }
+
+    fn register(&mut self, name: &str, origin: TemplateOrigin, source: String) -> Result<()> {
+        self.handlebars.register_template_string(name, &source)?;
+        self.handlebars.register_partial(name, &source)?;
+        self.templates.insert(name.to_string(), (origin, source));
+        Ok(())
+    }
+}
+
+/// `$CHANGELOGGEN_TEMPLATE_DIR`, else `templates` in the user config
+/// directory (e.g. `~/.config/changeloggen-cli/templates`).
+pub fn user_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("CHANGELOGGEN_TEMPLATE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    directories::ProjectDirs::from("", "", "changeloggen-cli")
+        .map(|dirs| dirs.config_dir().join("templates"))
+}
+
+/// Renders commits with the default template.
+pub struct HandlebarsRenderer;
+
+#[async_trait::async_trait]
+impl TemplateRenderer for HandlebarsRenderer {
+    async fn render(&self, commits: Vec<Commit>) -> Result<String> {
+        let notes = collect_notes(&commits, &BTreeMap::new(), &CommitParsers::default());
+        let context = TemplateContext::new(&notes, &commits);
+        TemplateRegistry::load()?.render(DEFAULT_TEMPLATE, &context)
+    }
+}
+
+handlebars_helper!(truncate: |text: str, length: u64| {
+    let length = length as usize;
+    match text.chars().count() > length {
+        true => {
+            let kept: String = text.chars().take(length.saturating_sub(1)).collect();
+            format!("{}…", kept.trim_end())
+        }
+        false => text.to_string(),
+    }
+});
 
-pub fn render(template_path: Option<&str>, data: &serde_json::Value) -> Result<String> {
-    let mut hb = Handlebars::new();
+handlebars_helper!(group_by: |items: array, key: str| {
+    let mut groups: Vec<(Value, Vec<Value>)> = Vec::new();
+    for item in items {
+        let value = item.get(key).cloned().unwrap_or(Value::Null);
+        match groups.iter_mut().find(|(group, _)| *group == value) {
+            Some((_, members)) => members.push(item.clone()),
+            None => groups.push((value, vec![item.clone()])),
+        }
+    }
+    Value::Array(
+        groups
+            .into_iter()
+            .map(|(key, items)| serde_json::json!({ "key": key, "items": items }))
+            .collect(),
+    )
+});
 
-    let template = if let Some(path) = template_path {
-        fs::read_to_string(path)?
+/// Helpers available to every template:
+///
+/// - `{{format_date date "%B %-d, %Y"}}` reformats a `YYYY-MM-DD` or
+///   RFC 3339 date, or a Unix timestamp
+/// - `{{truncate text 50}}` shortens text to at most 50 characters
+/// - `{{commit_link hash}}` links a short hash to the commit on the
+///   repository's forge
+/// - `{{pluralize count "fix" "fixes"}}` prints `1 fix` / `2 fixes`; the
+///   count may be an array, and the plural defaults to singular + `s`
+/// - `{{#each (group_by notes "scope")}}` iterates `{key, items}` groups in
+///   order of first appearance
+pub fn register_helpers(hb: &mut Handlebars) {
+    hb.register_helper("format_date", Box::new(format_date));
+    hb.register_helper("truncate", Box::new(truncate));
+    hb.register_helper("commit_link", Box::new(commit_link));
+    hb.register_helper("pluralize", Box::new(pluralize));
+    hb.register_helper("group_by", Box::new(group_by));
+}
+
+fn format_date(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let value = h
+        .param(0)
+        .ok_or(RenderErrorReason::ParamNotFoundForIndex("format_date", 0))?
+        .value();
+    let format = h
+        .param(1)
+        .and_then(|param| param.value().as_str())
+        .unwrap_or("%Y-%m-%d");
+
+    let formatted = match value {
+        Value::Number(seconds) => seconds
+            .as_i64()
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+            .map(|date| date.format(format).to_string()),
+        Value::String(text) => DateTime::parse_from_rfc3339(text)
+            .map(|date| date.format(format).to_string())
+            .or_else(|_| {
+                NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                    .map(|date| date.format(format).to_string())
+            })
+            .ok(),
+        Value::Null => Some(String::new()),
+        _ => None,
+    };
+    let formatted = formatted.ok_or_else(|| {
+        RenderErrorReason::Other(format!("format_date: cannot read '{}' as a date", value))
+    })?;
+    out.write(&formatted)?;
+    Ok(())
+}
+
+/// `[abc1234](https://.../commit/<hash>)` when the context has a
+/// `repository`, else the bare short hash.
+fn commit_link(
+    h: &Helper,
+    _: &Handlebars,
+    ctx: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let Some(hash) = h.param(0).and_then(|param| param.value().as_str()) else {
+        return Ok(());
+    };
+
+    let repository = ctx.data().get("repository");
+    let forge = repository.and_then(|repository| {
+        Some(Forge {
+            kind: ForgeKind::parse(repository.get("kind")?.as_str()?)?,
+            base_url: repository.get("url")?.as_str()?.to_string(),
+        })
+    });
+    match forge {
+        Some(forge) => out.write(&format!(
+            "[{}]({})",
+            short_hash(hash),
+            forge.commit_url(hash)
+        ))?,
+        None => out.write(&short_hash(hash))?,
+    }
+    Ok(())
+}
+
+fn pluralize(
+    h: &Helper,
+    _: &Handlebars,
+    _: &Context,
+    _: &mut RenderContext,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let count = match h
+        .param(0)
+        .ok_or(RenderErrorReason::ParamNotFoundForIndex("pluralize", 0))?
+        .value()
+    {
+        Value::Array(items) => items.len() as u64,
+        value => value.as_u64().ok_or_else(|| {
+            RenderErrorReason::Other(format!("pluralize: '{}' is not a count", value))
+        })?,
+    };
+    let singular = h
+        .param(1)
+        .and_then(|param| param.value().as_str())
+        .ok_or(RenderErrorReason::ParamNotFoundForIndex("pluralize", 1))?;
+    let plural = match h.param(2).and_then(|param| param.value().as_str()) {
+        Some(plural) => plural.to_string(),
+        None => format!("{}s", singular),
+    };
+
+    let word = if count == 1 {
+        singular
     } else {
-        include_str!("../../templates/default.hbs").to_string()
+        plural.as_str()
     };
+    out.write(&format!("{} {}", count, word))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::changelog::ChangelogDocument;
+    use crate::core::context::DocumentContext;
+    use crate::core::notes::PullRequestLink;
+
+    fn render_str(template: &str, data: &Value) -> String {
+        let mut hb = Handlebars::new();
+        hb.register_escape_fn(handlebars::no_escape);
+        register_helpers(&mut hb);
+        hb.render_template(template, data).expect("render")
+    }
+
+    #[test]
+    fn formats_dates_and_counts() {
+        let data = serde_json::json!({"date": "2026-03-04", "at": 1_767_225_600, "notes": [1, 2]});
+        assert_eq!(
+            render_str("{{format_date date \"%B %-d, %Y\"}}", &data),
+            "March 4, 2026"
+        );
+        assert_eq!(render_str("{{format_date at}}", &data), "2026-01-01");
+        assert_eq!(
+            render_str(
+                "{{pluralize notes \"fix\" \"fixes\"}}, {{pluralize 1 \"commit\"}}",
+                &data
+            ),
+            "2 fixes, 1 commit"
+        );
+        assert_eq!(
+            render_str("{{truncate \"a long description\" 6}}", &data),
+            "a lon…"
+        );
+    }
+
+    #[test]
+    fn default_template_matches_generate_output() {
+        let commits = vec![
+            Commit::new("0123456789", "feat(api): add endpoint"),
+            Commit::new("abcdef0123", "fix: resolve bug"),
+        ];
+        let mut notes = collect_notes(&commits, &BTreeMap::new(), &CommitParsers::default());
+        notes[1].pull_request = Some(PullRequestLink {
+            number: 7,
+            url: "https://github.com/acme/widgets/pull/7".to_string(),
+            author: Some("alice".to_string()),
+        });
+
+        let markdown = TemplateRegistry::builtin()
+            .render(DEFAULT_TEMPLATE, &TemplateContext::new(&notes, &commits))
+            .expect("render");
+        assert_eq!(
+            markdown,
+            "### Added\n- **api:** add endpoint\n\n### Fixed\n- resolve bug ([#7](https://github.com/acme/widgets/pull/7)) by @alice\n\n"
+        );
+    }
+
+    #[test]
+    fn document_template_matches_built_in_layout() {
+        let doc = ChangelogDocument::parse(
+            "# Changelog\n\nAll notable changes.\n\n## [Unreleased]\n\n### Added\n- pending\n\n## 1.1.0 - 2026-01-02\n\nHighlights first.\n\n### Added\n- export\n  - nested detail\n\n### Fixed\n- crash\n\n## [1.0.0] - 2025-12-01\n\n[1.0.0]: https://example.com/releases/tag/v1.0.0\n",
+        )
+        .expect("parse changelog");
+
+        let rendered = TemplateRegistry::builtin()
+            .render("document", &DocumentContext::new(&doc))
+            .expect("render");
+        assert_eq!(rendered.trim_end().to_string() + "\n", doc.to_markdown());
+    }
+
+    #[test]
+    fn resolves_template_files_with_sibling_partials() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("note.hbs"), "* {{text}}").expect("write partial");
+        fs::write(
+            dir.path().join("notes.hbs"),
+            "{{#each sections}}{{#each notes}}{{> note}}\n{{/each}}{{/each}}",
+        )
+        .expect("write template");
+
+        let mut registry = TemplateRegistry::builtin();
+        let name = registry
+            .resolve(&dir.path().join("notes.hbs"))
+            .expect("resolve file");
+        assert_eq!(name, "notes");
+        assert_eq!(
+            registry.origin("note"),
+            Some(&TemplateOrigin::File(dir.path().join("note.hbs")))
+        );
+
+        let commits = vec![Commit::new("0123456789", "feat: add endpoint")];
+        let context = TemplateContext::new(
+            &collect_notes(&commits, &BTreeMap::new(), &CommitParsers::default()),
+            &commits,
+        );
+        assert_eq!(
+            registry.render(&name, &context).expect("render"),
+            "* add endpoint\n"
+        );
+
+        assert_eq!(
+            registry.resolve(Path::new("slack")).expect("built-in"),
+            "slack"
+        );
+        let error = registry
+            .resolve(Path::new("missing"))
+            .expect_err("unknown name");
+        assert!(error
+            .to_string()
+            .contains("compact, document, github-release"));
+    }
 
-    hb.register_template_string("tpl", template)?;
-    Ok(hb.render("tpl", data)?)
+    #[test]
+    fn links_commits_and_groups_notes() {
+        let data = serde_json::json!({
+            "repository": {"url": "https://gitlab.com/acme/widgets", "kind": "gitlab"},
+            "notes": [
+                {"scope": "api", "text": "a"},
+                {"scope": null, "text": "b"},
+                {"scope": "api", "text": "c"},
+            ],
+        });
+        assert_eq!(
+            render_str("{{commit_link \"0123456789\"}}", &data),
+            "[0123456](https://gitlab.com/acme/widgets/-/commit/0123456789)"
+        );
+        assert_eq!(
+            render_str(
+                "{{#each (group_by notes \"scope\")}}[{{key}}:{{#each items}}{{text}}{{/each}}]{{/each}}",
+                &data
+            ),
+            "[api:ac][:b]"
+        );
+        assert_eq!(
+            render_str("{{commit_link \"0123456789\"}}", &serde_json::json!({})),
+            "0123456"
+        );
+    }
 }
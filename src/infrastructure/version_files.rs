@@ -0,0 +1,429 @@
+use crate::infrastructure::workspace::detect_workspace;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use semver::Version;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// How the version is stored in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionFileKind {
+    /// `[package]` and `[workspace.package]` versions.
+    CargoToml,
+    /// `[[package]]` entries of the crates released with it.
+    CargoLock,
+    /// Top-level `"version"`.
+    PackageJson,
+    /// `[project]` or `[tool.poetry]` version.
+    PyProject,
+    /// The whole file, e.g. `VERSION`.
+    Plain,
+    /// A regex whose `version` group (or first group) holds the version.
+    Pattern(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionFile {
+    pub path: PathBuf,
+    pub kind: VersionFileKind,
+}
+
+impl VersionFile {
+    /// Picks the kind from the file name unless a pattern is given.
+    pub fn new(path: PathBuf, pattern: Option<String>) -> Self {
+        let kind = match (pattern, path.file_name().and_then(|n| n.to_str())) {
+            (Some(pattern), _) => VersionFileKind::Pattern(pattern),
+            (None, Some("Cargo.toml")) => VersionFileKind::CargoToml,
+            (None, Some("Cargo.lock")) => VersionFileKind::CargoLock,
+            (None, Some("package.json")) => VersionFileKind::PackageJson,
+            (None, Some("pyproject.toml")) => VersionFileKind::PyProject,
+            _ => VersionFileKind::Plain,
+        };
+        Self { path, kind }
+    }
+
+    pub fn read_version(&self) -> Result<Option<Version>> {
+        let content = read(&self.path)?;
+        let Some(raw) = find_version(&self.kind, &content)? else {
+            return Ok(None);
+        };
+        Version::parse(&raw)
+            .map(Some)
+            .map_err(|e| anyhow!("{}: invalid version '{}': {}", self.path.display(), raw, e))
+    }
+}
+
+/// Writes `version` into `files`, keeping their formatting. Cargo.lock
+/// entries are updated for the crates of the listed Cargo.toml files
+/// (including workspace members inheriting the workspace version). Returns
+/// the files that changed.
+pub fn bump_version_files(files: &[VersionFile], version: &Version) -> Result<Vec<PathBuf>> {
+    let crates = released_crates(files)?;
+
+    let mut changed = Vec::new();
+    for file in files {
+        let content = read(&file.path)?;
+        let updated = set_version(&file.kind, &content, version, &crates)
+            .map_err(|e| anyhow!("{}: {}", file.path.display(), e))?;
+        if updated != content {
+            fs::write(&file.path, updated)?;
+            changed.push(file.path.clone());
+        }
+    }
+    Ok(changed)
+}
+
+/// The version stored in `content`, if any.
+pub fn find_version(kind: &VersionFileKind, content: &str) -> Result<Option<String>> {
+    let range = match kind {
+        VersionFileKind::CargoToml => {
+            toml_string_value(content, &["package", "workspace.package"], "version")
+                .into_iter()
+                .next()
+        }
+        VersionFileKind::PyProject => {
+            toml_string_value(content, &["project", "tool.poetry"], "version")
+                .into_iter()
+                .next()
+        }
+        VersionFileKind::PackageJson => json_version(content),
+        VersionFileKind::Plain => plain_version(content),
+        VersionFileKind::Pattern(pattern) => pattern_versions(pattern, content)?.into_iter().next(),
+        VersionFileKind::CargoLock => None,
+    };
+    Ok(range.map(|range| content[range].to_string()))
+}
+
+/// `content` with its version(s) replaced by `version`. `crates` names the
+/// Cargo.lock packages to update.
+pub fn set_version(
+    kind: &VersionFileKind,
+    content: &str,
+    version: &Version,
+    crates: &[String],
+) -> Result<String> {
+    let ranges = match kind {
+        VersionFileKind::CargoToml => {
+            toml_string_value(content, &["package", "workspace.package"], "version")
+        }
+        VersionFileKind::PyProject => {
+            toml_string_value(content, &["project", "tool.poetry"], "version")
+        }
+        VersionFileKind::PackageJson => json_version(content).into_iter().collect(),
+        VersionFileKind::Plain => plain_version(content).into_iter().collect(),
+        VersionFileKind::Pattern(pattern) => pattern_versions(pattern, content)?,
+        VersionFileKind::CargoLock => lock_versions(content, crates),
+    };
+
+    if ranges.is_empty() && *kind != VersionFileKind::CargoLock {
+        return Err(anyhow!("no version found"));
+    }
+
+    let mut out = content.to_string();
+    // Replace back to front so earlier ranges stay valid.
+    for range in ranges.into_iter().rev() {
+        out.replace_range(range, &version.to_string());
+    }
+    Ok(out)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| anyhow!("cannot read {}: {}", path.display(), e))
+}
+
+/// Names of the crates whose versions the listed Cargo.toml files set.
+fn released_crates(files: &[VersionFile]) -> Result<Vec<String>> {
+    let name = Regex::new(r#"^\s*name\s*=\s*"([^"]+)""#).expect("valid regex");
+    let inherits = Regex::new(
+        r"(?m)^\s*version\s*(\.\s*workspace\s*=\s*true|=\s*\{[^}]*workspace\s*=\s*true)",
+    )
+    .expect("valid regex");
+
+    let mut crates = Vec::new();
+    for file in files {
+        if file.kind != VersionFileKind::CargoToml {
+            continue;
+        }
+        let content = read(&file.path)?;
+        let package = toml_table_lines(&content, &["package"]);
+        if let Some(found) = package.iter().find_map(|line| name.captures(line)) {
+            crates.push(found[1].to_string());
+        }
+
+        if toml_string_value(&content, &["workspace.package"], "version").is_empty() {
+            continue;
+        }
+        let root = file.path.parent().unwrap_or(Path::new("."));
+        for member in detect_workspace(root)? {
+            let manifest = read(&root.join(&member.path).join("Cargo.toml"))?;
+            if inherits.is_match(&manifest) {
+                crates.push(member.name);
+            }
+        }
+    }
+    Ok(crates)
+}
+
+/// Lines of the given TOML tables (`[package]`, not `[[package]]`).
+fn toml_table_lines<'a>(content: &'a str, tables: &[&str]) -> Vec<&'a str> {
+    let mut current = String::new();
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        if let Some(header) = table_header(line) {
+            current = header;
+            continue;
+        }
+        if tables.contains(&current.as_str()) {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Byte ranges of `key = "..."` values (inside the quotes) in `tables`.
+fn toml_string_value(content: &str, tables: &[&str], key: &str) -> Vec<Range<usize>> {
+    let pattern =
+        Regex::new(&format!(r#"^\s*{}\s*=\s*"([^"]*)""#, regex::escape(key))).expect("valid regex");
+
+    let mut current = String::new();
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if let Some(header) = table_header(line) {
+            current = header;
+        } else if tables.contains(&current.as_str()) {
+            if let Some(value) = pattern.captures(line).and_then(|c| c.get(1)) {
+                ranges.push(offset + value.start()..offset + value.end());
+            }
+        }
+        offset += line.len();
+    }
+    ranges
+}
+
+fn table_header(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.starts_with("[[") {
+        return Some(
+            line.trim_matches(|c| c == '[' || c == ']')
+                .trim()
+                .to_string()
+                + "[]",
+        );
+    }
+    let inner = line.strip_prefix('[')?;
+    let end = inner.find(']')?;
+    Some(inner[..end].trim().to_string())
+}
+
+/// Versions of the `[[package]]` entries named in `crates` that come from
+/// this workspace (no `source`).
+fn lock_versions(content: &str, crates: &[String]) -> Vec<Range<usize>> {
+    let name = Regex::new(r#"^name\s*=\s*"([^"]+)""#).expect("valid regex");
+    let version = Regex::new(r#"^version\s*=\s*"([^"]*)""#).expect("valid regex");
+
+    struct Entry {
+        name: Option<String>,
+        version: Option<Range<usize>>,
+        has_source: bool,
+    }
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if line.trim() == "[[package]]" {
+            entries.push(Entry {
+                name: None,
+                version: None,
+                has_source: false,
+            });
+        } else if let Some(entry) = entries.last_mut() {
+            if let Some(c) = name.captures(line) {
+                entry.name = Some(c[1].to_string());
+            } else if let Some(value) = version.captures(line).and_then(|c| c.get(1)) {
+                entry.version = Some(offset + value.start()..offset + value.end());
+            } else if line.starts_with("source") {
+                entry.has_source = true;
+            }
+        }
+        offset += line.len();
+    }
+
+    entries
+        .into_iter()
+        .filter(|entry| !entry.has_source)
+        .filter(|entry| entry.name.as_ref().is_some_and(|n| crates.contains(n)))
+        .filter_map(|entry| entry.version)
+        .collect()
+}
+
+/// Span of the top-level `"version"` string. Keys of nested objects, such
+/// as `engines` or lock file entries, are skipped.
+fn json_version(content: &str) -> Option<Range<usize>> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let expected = value.get("version")?.as_str()?;
+
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut expect_key = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => {
+                depth += 1;
+                expect_key = true;
+            }
+            b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b',' => expect_key = true,
+            b'"' => {
+                let end = json_string_end(bytes, i)?;
+                if depth == 1 && expect_key && &content[i + 1..end] == "version" {
+                    let colon = end + 1 + content[end + 1..].find(':')?;
+                    let start = colon + 1 + content[colon + 1..].find('"')?;
+                    let range = start + 1..json_string_end(bytes, start)?;
+                    return (&content[range.clone()] == expected).then_some(range);
+                }
+                expect_key = false;
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the quote closing the JSON string that opens at `start`.
+fn json_string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+fn plain_version(content: &str) -> Option<Range<usize>> {
+    let start = content.len() - content.trim_start().len();
+    let end = content.trim_end().len();
+    (start < end).then_some(start..end)
+}
+
+fn pattern_versions(pattern: &str, content: &str) -> Result<Vec<Range<usize>>> {
+    let regex = Regex::new(pattern).map_err(|e| anyhow!("invalid version pattern: {}", e))?;
+    Ok(regex
+        .captures_iter(content)
+        .filter_map(|c| c.name("version").or_else(|| c.get(1)))
+        .map(|m| m.range())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(raw: &str) -> Version {
+        Version::parse(raw).expect("version")
+    }
+
+    #[test]
+    fn bumps_manifests_keeping_formatting() {
+        let cargo = "[package]\nname = \"app\"   # main crate\nversion    = \"0.1.0\"\n\n[dependencies]\nserde = { version = \"1.0.0\" }\n\n[workspace.package]\nversion = \"0.1.0\"\n";
+        assert_eq!(
+            set_version(&VersionFileKind::CargoToml, cargo, &v("0.2.0"), &[]).expect("cargo"),
+            cargo.replace("\"0.1.0\"", "\"0.2.0\"")
+        );
+        assert_eq!(
+            find_version(&VersionFileKind::CargoToml, cargo).expect("find"),
+            Some("0.1.0".to_string())
+        );
+
+        let json =
+            "{\n  \"name\": \"app\",\n  \"version\": \"1.2.3\",\n  \"dependencies\": {}\n}\n";
+        assert!(
+            set_version(&VersionFileKind::PackageJson, json, &v("1.3.0"), &[])
+                .expect("json")
+                .contains("\"version\": \"1.3.0\",\n")
+        );
+
+        let nested = "{\n  \"engines\": {\"version\": \"18.0.0\"},\n  \"deps\": [{\"version\": \"2.0.0\"}],\n  \"version\": \"1.2.3\"\n}\n";
+        assert_eq!(
+            find_version(&VersionFileKind::PackageJson, nested).expect("find"),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(
+            set_version(&VersionFileKind::PackageJson, nested, &v("1.3.0"), &[]).expect("json"),
+            nested.replace("\"1.2.3\"", "\"1.3.0\"")
+        );
+
+        let pyproject =
+            "[tool.black]\nversion = \"x\"\n\n[tool.poetry]\nname = \"app\"\nversion = \"1.0.0\"\n";
+        assert!(
+            set_version(&VersionFileKind::PyProject, pyproject, &v("1.1.0"), &[])
+                .expect("pyproject")
+                .ends_with("version = \"1.1.0\"\n")
+        );
+
+        assert_eq!(
+            set_version(&VersionFileKind::Plain, "1.0.0\n", &v("2.0.0"), &[]).expect("plain"),
+            "2.0.0\n"
+        );
+        let header = "#define APP_VERSION \"1.0.0\"\n";
+        let pattern = VersionFileKind::Pattern(r#"APP_VERSION "(?P<version>[^"]+)""#.to_string());
+        assert_eq!(
+            set_version(&pattern, header, &v("1.0.1"), &[]).expect("pattern"),
+            "#define APP_VERSION \"1.0.1\"\n"
+        );
+    }
+
+    #[test]
+    fn bumps_only_local_lock_entries() {
+        let lock = "[[package]]\nname = \"app\"\nversion = \"0.1.0\"\n\n[[package]]\nname = \"serde\"\nversion = \"0.1.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"other\"\nversion = \"0.1.0\"\n";
+        let updated = set_version(
+            &VersionFileKind::CargoLock,
+            lock,
+            &v("0.2.0"),
+            &["app".to_string(), "serde".to_string()],
+        )
+        .expect("lock");
+        assert_eq!(updated.matches("0.2.0").count(), 1);
+        assert!(updated.starts_with("[[package]]\nname = \"app\"\nversion = \"0.2.0\"\n"));
+    }
+
+    #[test]
+    fn follows_workspace_inherited_versions_into_the_lock_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\"]\n\n[workspace.package]\nversion = \"1.0.0\"\n",
+        )
+        .expect("root manifest");
+        fs::create_dir_all(root.join("core")).expect("mkdir");
+        fs::write(
+            root.join("core/Cargo.toml"),
+            "[package]\nname = \"app-core\"\nversion.workspace = true\n",
+        )
+        .expect("member manifest");
+        fs::write(
+            root.join("Cargo.lock"),
+            "[[package]]\nname = \"app-core\"\nversion = \"1.0.0\"\n",
+        )
+        .expect("lock");
+
+        let files = vec![
+            VersionFile::new(root.join("Cargo.toml"), None),
+            VersionFile::new(root.join("Cargo.lock"), None),
+        ];
+        assert_eq!(files[0].read_version().expect("read"), Some(v("1.0.0")));
+
+        let changed = bump_version_files(&files, &v("1.1.0")).expect("bump");
+        assert_eq!(changed.len(), 2);
+        let lock = fs::read_to_string(root.join("Cargo.lock")).expect("lock");
+        assert!(lock.contains("version = \"1.1.0\""));
+    }
+}
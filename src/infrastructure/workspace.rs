@@ -1,12 +1,241 @@
-use anyhow::Result;
+use crate::core::git::wildcard_match;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
 use std::fs;
+use std::path::{Component, Path, PathBuf};
 
-pub fn detect_workspace() -> Result<Vec<String>> {
-    let cargo = fs::read_to_string("Cargo.toml")?;
-    if cargo.contains("[workspace]") {
-        println!("Workspace detected.");
-        // parse members (simplified)
-        return Ok(vec!["crate-a".into(), "crate-b".into()]);
+/// A crate of a Cargo workspace, with its path relative to the workspace root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    package: Option<Package>,
+    workspace: Option<Workspace>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Workspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+impl WorkspaceMember {
+    /// Tag prefix for this crate, e.g. `mycrate-v` for prefix `v`.
+    pub fn tag_prefix(&self, prefix: &str) -> String {
+        format!("{}-{}", self.name, prefix)
+    }
+}
+
+/// Reads `[workspace] members` and `exclude` from `<root>/Cargo.toml` and
+/// expands their globs to member crates. A root `[package]` is included
+/// with an empty path. Returns an empty list when the manifest has no
+/// `[workspace]` table.
+pub fn detect_workspace(root: &Path) -> Result<Vec<WorkspaceMember>> {
+    let manifest = read_manifest(&root.join("Cargo.toml"))?;
+    let Some(workspace) = manifest.workspace else {
+        return Ok(Vec::new());
+    };
+
+    // A root `[package]` is a member whether or not `members` lists `.`.
+    let mut members: Vec<WorkspaceMember> = manifest
+        .package
+        .map(|package| WorkspaceMember {
+            name: package.name,
+            path: PathBuf::new(),
+        })
+        .into_iter()
+        .collect();
+    for pattern in &workspace.members {
+        for path in expand_glob(root, pattern)? {
+            if path.as_os_str().is_empty() {
+                continue;
+            }
+            let excluded = workspace
+                .exclude
+                .iter()
+                .any(|exclude| path_matches(&path, exclude));
+            if excluded || members.iter().any(|m: &WorkspaceMember| m.path == path) {
+                continue;
+            }
+
+            let manifest_path = root.join(&path).join("Cargo.toml");
+            if !manifest_path.is_file() {
+                continue;
+            }
+            let package = read_manifest(&manifest_path)?
+                .package
+                .ok_or_else(|| anyhow!("{} has no [package] table", manifest_path.display()))?;
+            members.push(WorkspaceMember {
+                name: package.name,
+                path,
+            });
+        }
+    }
+
+    members.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(members)
+}
+
+fn read_manifest(path: &Path) -> Result<Manifest> {
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("cannot read {}: {}", path.display(), e))?;
+    toml::from_str(&content).map_err(|e| anyhow!("invalid {}: {}", path.display(), e))
+}
+
+/// Expands a Cargo member glob (`*` and `?` within a path component) to the
+/// matching directories below `root`, relative to it.
+fn expand_glob(root: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let mut matches = vec![PathBuf::new()];
+
+    for component in normalize(Path::new(pattern)).components() {
+        let Component::Normal(part) = component else {
+            continue;
+        };
+        let part = part.to_string_lossy();
+
+        let mut next = Vec::new();
+        for base in &matches {
+            if !part.contains(['*', '?']) {
+                let candidate = base.join(part.as_ref());
+                if root.join(&candidate).is_dir() {
+                    next.push(candidate);
+                }
+                continue;
+            }
+
+            let Ok(entries) = fs::read_dir(root.join(base)) else {
+                continue;
+            };
+            for entry in entries {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.file_type()?.is_dir() && wildcard_match(&part, &name) {
+                    next.push(base.join(name));
+                }
+            }
+        }
+        matches = next;
+    }
+
+    matches.sort();
+    Ok(matches)
+}
+
+fn path_matches(path: &Path, pattern: &str) -> bool {
+    let pattern = normalize(Path::new(pattern));
+    let parts: Vec<_> = pattern.components().collect();
+    let names: Vec<_> = path.components().collect();
+
+    parts.len() == names.len()
+        && parts.iter().zip(&names).all(|(p, n)| {
+            wildcard_match(
+                &p.as_os_str().to_string_lossy(),
+                &n.as_os_str().to_string_lossy(),
+            )
+        })
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let full = root.join(path);
+        fs::create_dir_all(full.parent().expect("parent")).expect("mkdir");
+        fs::write(full, content).expect("write");
+    }
+
+    #[test]
+    fn keeps_the_root_package() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\".\", \"crates/*\"]\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"app-core\"\n",
+        );
+
+        let members = detect_workspace(root).expect("workspace");
+        assert_eq!(
+            members,
+            vec![
+                WorkspaceMember {
+                    name: "app".to_string(),
+                    path: PathBuf::new(),
+                },
+                WorkspaceMember {
+                    name: "app-core".to_string(),
+                    path: PathBuf::from("crates/core"),
+                },
+            ]
+        );
+
+        write(
+            root,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        assert_eq!(detect_workspace(root).expect("workspace").len(), 2);
+    }
+
+    #[test]
+    fn expands_members_and_excludes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/legacy-*\"]\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"app-core\"\n",
+        );
+        write(
+            root,
+            "crates/legacy-io/Cargo.toml",
+            "[package]\nname = \"legacy\"\n",
+        );
+        write(root, "crates/notes/README.md", "not a crate");
+        write(
+            root,
+            "tools/cli/Cargo.toml",
+            "[package]\nname = \"app-cli\"\n",
+        );
+
+        let members = detect_workspace(root).expect("workspace");
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["app-core", "app-cli"]);
+        assert_eq!(members[1].path, PathBuf::from("tools/cli"));
+    }
+
+    #[test]
+    fn matches_wildcards() {
+        assert!(wildcard_match("legacy-*", "legacy-io"));
+        assert!(wildcard_match("c?re", "core"));
+        assert!(!wildcard_match("legacy-*", "core"));
     }
-    Ok(vec![])
 }
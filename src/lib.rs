@@ -6,7 +6,6 @@ pub mod core;
 pub mod domain;
 pub mod error;
 pub mod infrastructure;
-pub mod parser;
 pub mod traits;
 
 pub use error::{ChangelogError, Result};
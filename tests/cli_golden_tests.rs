@@ -40,6 +40,22 @@ fn new_creates_scaffold_and_validate_passes() {
         .args(["validate", "--file", "CHANGELOG.md"])
         .assert()
         .success();
+
+    for args in [
+        ["new", "--file", "CHANGELOG.txt", "--format", "rss"],
+        ["new", "--file", "CHANGELOG.txt", "--format", "atom"],
+        ["new", "--file", "changelog.html", "--format", "html"],
+    ] {
+        bin_cmd()
+            .current_dir(dir.path())
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "new can only create markdown, asciidoc, rst, json, yaml or toml changelogs",
+            ));
+    }
+    assert!(!dir.path().join("changelog.html").exists());
 }
 
 #[test]
@@ -1,11 +1,112 @@
-use changelog_gen::core::changelog::ChangelogDocument;
+use changelog_gen::core::changelog::{ChangelogDocument, ReleaseVersion};
 
 #[test]
 fn fixture_roundtrip_is_stable() {
-    let fixture = include_str!("fixtures/changelog_roundtrip.md");
+    for fixture in [
+        include_str!("fixtures/changelog_roundtrip.md"),
+        include_str!("fixtures/changelog_section_order.md"),
+    ] {
+        let parsed = ChangelogDocument::parse(fixture).expect("parse fixture");
+        parsed.validate(true).expect("validate fixture");
+        let rendered = parsed.to_markdown();
+        assert_eq!(rendered, fixture);
+        let reparsed = ChangelogDocument::parse(&rendered).expect("reparse");
+        assert_eq!(parsed, reparsed);
+    }
+}
+
+#[test]
+fn prose_links_and_nested_notes_are_preserved() {
+    let fixture = include_str!("fixtures/changelog_prose.md");
     let parsed = ChangelogDocument::parse(fixture).expect("parse fixture");
     parsed.validate(true).expect("validate fixture");
+
+    assert!(parsed.preamble.starts_with("All notable changes"));
+    assert_eq!(
+        parsed.releases[1].description,
+        "This release focuses on parser robustness."
+    );
+    assert_eq!(
+        parsed.releases[1].sections["Added"][1],
+        "nested notes\n  - first detail\n  - second detail"
+    );
+    assert_eq!(parsed.links.len(), 3);
+    assert_eq!(parsed.to_markdown(), fixture);
+}
+
+#[test]
+fn removing_a_release_keeps_hand_written_content() {
+    let fixture = include_str!("fixtures/changelog_prose.md");
+    let mut parsed = ChangelogDocument::parse(fixture).expect("parse fixture");
+    let target = ReleaseVersion::parse("1.0.0").expect("version");
+    assert!(parsed.remove_version(&target));
+
     let rendered = parsed.to_markdown();
-    let reparsed = ChangelogDocument::parse(&rendered).expect("reparse");
+    assert!(rendered.contains("adheres to [Semantic Versioning]"));
+    assert!(rendered.contains("This release focuses on parser robustness."));
+    assert!(rendered.contains("  - second detail"));
+    assert!(rendered.contains("[1.1.0]: https://github.com/o/r/compare/v1.0.0...v1.1.0"));
+    assert!(!rendered.contains("[1.0.0]:"));
+}
+
+#[test]
+fn prose_before_the_first_note_is_kept_as_the_section_description() {
+    let fixture = include_str!("fixtures/changelog_section_prose.md");
+    let parsed = ChangelogDocument::parse(fixture).expect("parse fixture");
+    parsed.validate(true).expect("validate fixture");
+
+    let release = &parsed.releases[0];
+    assert_eq!(
+        release.section_description("Changed"),
+        "The configuration format moved from YAML to TOML."
+    );
+    assert_eq!(release.sections["Changed"].len(), 2);
+    assert_eq!(release.section_description("Fixed"), "");
+    assert_eq!(parsed.to_markdown(), fixture);
+}
+
+#[test]
+fn unindented_text_after_a_blank_line_ends_the_note() {
+    let fixture = include_str!("fixtures/changelog_trailing_prose.md");
+    let parsed = ChangelogDocument::parse(fixture).expect("parse fixture");
+
+    let release = &parsed.releases[0];
+    assert_eq!(
+        release.sections["Changed"],
+        ["`changelog.yaml` is no longer read\n  and is ignored when present"]
+    );
+    assert_eq!(
+        release.section_description("Changed"),
+        "See the migration guide for details."
+    );
+
+    let reparsed = ChangelogDocument::parse(&parsed.to_markdown()).expect("reparse");
     assert_eq!(parsed, reparsed);
 }
+
+#[test]
+fn headings_inside_code_fences_are_text() {
+    let fixture = include_str!("fixtures/changelog_code_fence.md");
+    let parsed = ChangelogDocument::parse(fixture).expect("parse fixture");
+    parsed.validate(true).expect("validate fixture");
+
+    let versions: Vec<String> = parsed
+        .releases
+        .iter()
+        .map(|release| release.version.to_string())
+        .collect();
+    assert_eq!(versions, ["1.2.0", "1.1.0"]);
+
+    let release = &parsed.releases[0];
+    assert!(release.description.contains("## not a release"));
+    assert_eq!(
+        release.sections.keys().collect::<Vec<_>>(),
+        ["Added", "Fixed"]
+    );
+    assert!(release.sections["Added"][0].ends_with("  ### {{name}}\n  ```"));
+    assert_eq!(release.sections["Fixed"], ["crash on empty tags"]);
+    assert!(release
+        .section_description("Fixed")
+        .contains("- not a note"));
+    assert_eq!(parsed.to_markdown(), fixture);
+}
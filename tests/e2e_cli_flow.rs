@@ -56,7 +56,7 @@ fn release_generate_show_and_remove_flow() {
         .success();
 
     let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
-    assert!(changelog.contains("## 0.0.1 -"));
+    assert!(changelog.contains("## 0.1.1 -"));
     assert!(changelog.contains("### Fixed"));
     assert_eq!(changelog.matches("- resolve bug").count(), 1);
 
@@ -86,7 +86,7 @@ fn release_generate_show_and_remove_flow() {
             "--file",
             "CHANGELOG.md",
             "--range",
-            "0.0.1..0.2.0",
+            "0.1.1..0.2.0",
             "--converge",
         ])
         .assert()
@@ -123,7 +123,7 @@ fn release_generate_show_and_remove_flow() {
         .args([
             "remove",
             "--version",
-            "0.0.1",
+            "0.1.1",
             "--file",
             "CHANGELOG.md",
             "--yes",
@@ -132,6 +132,1443 @@ fn release_generate_show_and_remove_flow() {
         .success();
 
     let after_remove = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
-    assert!(!after_remove.contains("0.0.1"));
+    assert!(!after_remove.contains("0.1.1"));
     assert!(after_remove.contains("0.2.0"));
 }
+
+#[test]
+fn release_promotes_unreleased_notes() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+
+    commit_file(dir.path(), "a.txt", "a1", "feat: initial feature");
+    run_git(dir.path(), &["tag", "v0.1.0"]);
+    commit_file(dir.path(), "b.txt", "b1", "fix: resolve bug");
+
+    fs::write(
+        dir.path().join("CHANGELOG.md"),
+        "# Changelog\n\n## [Unreleased]\n\n### Added\n- hand-written entry\n\n## [0.1.0] - 2026-01-01\n\n### Added\n- initial feature\n",
+    )
+    .expect("write changelog");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--bump", "minor", "--file", "CHANGELOG.md"])
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    let unreleased = changelog.find("## [Unreleased]").expect("unreleased kept");
+    let release = changelog.find("## [0.2.0] -").expect("new release");
+    let entry = changelog.find("- hand-written entry").expect("entry moved");
+    assert!(unreleased < release && release < entry);
+    assert!(changelog.contains("- resolve bug"));
+    assert_eq!(changelog.matches("hand-written entry").count(), 1);
+}
+
+#[test]
+fn release_writes_compare_link_footer() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    run_git(
+        dir.path(),
+        &["remote", "add", "origin", "git@gitlab.com:group/app.git"],
+    );
+
+    commit_file(dir.path(), "a.txt", "a1", "feat: initial feature");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--version", "0.1.0", "--tag-prefix", "app-"])
+        .assert()
+        .success();
+    run_git(dir.path(), &["tag", "app-0.1.0"]);
+
+    commit_file(dir.path(), "b.txt", "b1", "fix: resolve bug");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--version", "0.2.0", "--tag-prefix", "app-"])
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    assert!(changelog.ends_with(
+        "[0.2.0]: https://gitlab.com/group/app/-/compare/app-0.1.0...app-0.2.0\n\
+         [0.1.0]: https://gitlab.com/group/app/-/tags/app-0.1.0\n"
+    ));
+}
+
+#[test]
+fn release_auto_bump_infers_version_from_commits() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+
+    commit_file(dir.path(), "a.txt", "a1", "feat: initial feature");
+    run_git(dir.path(), &["tag", "v1.0.0"]);
+    fs::write(
+        dir.path().join("CHANGELOG.md"),
+        "# Changelog\n\n## [1.0.0] - 2026-01-01\n\n### Added\n- initial feature\n",
+    )
+    .expect("write changelog");
+
+    commit_file(dir.path(), "b.txt", "b1", "fix: resolve bug");
+    commit_file(dir.path(), "c.txt", "c1", "feat: add api");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--bump", "auto"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("minor bump from 2 commit(s)"))
+        .stdout(predicate::str::contains("feat: add api"));
+
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    assert!(changelog.contains("## [1.1.0] -"));
+
+    run_git(dir.path(), &["tag", "v1.1.0"]);
+    commit_file(dir.path(), "d.txt", "d1", "docs: explain api");
+    fs::write(dir.path().join("map.toml"), "[bump]\ndocs = \"none\"\n").expect("write map");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--bump", "auto", "--map", "map.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("found nothing to release"));
+}
+
+#[test]
+fn release_auto_bump_starts_from_the_latest_tag_without_a_changelog() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+
+    commit_file(dir.path(), "a.txt", "a1", "feat: initial feature");
+    run_git(dir.path(), &["tag", "v0.1.0"]);
+    commit_file(dir.path(), "b.txt", "b1", "feat!: new storage engine");
+    commit_file(dir.path(), "c.txt", "c1", "fix: storage crash");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--bump", "auto"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("minor bump from 2 commit(s)"));
+
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    assert!(changelog.contains("## [0.2.0] -"), "{}", changelog);
+    assert!(!changelog.contains("## [0.1.0]"));
+}
+
+#[test]
+fn prerelease_bumps_and_finalize_fold_entries() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+
+    commit_file(dir.path(), "a.txt", "a1", "feat: initial feature");
+    run_git(dir.path(), &["tag", "v1.0.0"]);
+    fs::write(
+        dir.path().join("CHANGELOG.md"),
+        "# Changelog\n\n## [1.0.0] - 2026-01-01\n\n### Added\n- initial feature\n",
+    )
+    .expect("write changelog");
+
+    commit_file(dir.path(), "b.txt", "b1", "feat!: new storage engine");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--bump", "premajor", "--preid", "rc"])
+        .assert()
+        .success();
+    run_git(dir.path(), &["tag", "v2.0.0-rc.1"]);
+
+    commit_file(dir.path(), "c.txt", "c1", "fix: storage crash");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--bump", "prerelease"])
+        .assert()
+        .success();
+    run_git(dir.path(), &["tag", "v2.0.0-rc.2"]);
+
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    assert!(changelog.contains("## [2.0.0-rc.2] -"));
+    assert!(changelog.contains("## [2.0.0-rc.1] -"));
+
+    commit_file(dir.path(), "d.txt", "d1", "fix: storage leak");
+    let changelog = changelog.replacen(
+        "## [2.0.0-rc.2]",
+        "## [Unreleased]\n\n### Fixed\n- hand-written fix\n\n## [2.0.0-rc.2]",
+        1,
+    );
+    fs::write(dir.path().join("CHANGELOG.md"), changelog).expect("write changelog");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--finalize"])
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    assert!(!changelog.contains("rc."));
+    let final_release = changelog.find("## [2.0.0] -").expect("final release");
+    let previous = changelog.find("## [1.0.0]").expect("previous release");
+    for note in ["new storage engine", "storage crash", "storage leak"] {
+        let at = changelog.find(note).expect("folded note");
+        assert!(final_release < at && at < previous, "{} not folded", note);
+    }
+    // Unreleased notes first, then pre-release notes, then generated ones.
+    let order: Vec<usize> = ["hand-written fix", "storage crash", "storage leak"]
+        .iter()
+        .map(|note| changelog.find(note).expect("note"))
+        .collect();
+    assert!(order.windows(2).all(|w| w[0] < w[1]), "{}", changelog);
+}
+
+#[test]
+fn generate_filters_and_groups_by_scope() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+
+    commit_file(dir.path(), "a.txt", "a1", "feat(api): add endpoint");
+    commit_file(dir.path(), "b.txt", "b1", "feat(cli): add flag");
+    commit_file(dir.path(), "c.txt", "c1", "chore(deps): bump serde");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["generate", "--exclude-scope", "deps"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- **api:** add endpoint"))
+        .stdout(predicate::str::contains("- **cli:** add flag"))
+        .stdout(predicate::str::contains("serde").not());
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["generate", "--scope", "api", "--scope-style", "grouped"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- **api:**\n  - add endpoint"))
+        .stdout(predicate::str::contains("add flag").not());
+}
+
+#[test]
+fn commit_parsers_classify_custom_messages() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    fs::write(
+        dir.path().join("changelog.toml"),
+        r#"[[commit_parsers]]
+pattern = '^:sparkles:'
+section = "Added"
+
+[[commit_parsers]]
+pattern = '^\[(?P<ticket>[A-Z]+-\d+)\] (?P<description>.+)'
+section = "Fixed"
+replace = "$description ($ticket)"
+
+[[commit_parsers]]
+pattern = '^Merge branch'
+skip = true
+"#,
+    )
+    .expect("write config");
+
+    commit_file(dir.path(), "a.txt", "a1", ":sparkles: add export");
+    commit_file(dir.path(), "b.txt", "b1", "[WEB-12] Fix login redirect");
+    commit_file(dir.path(), "c.txt", "c1", "Merge branch 'topic'");
+    commit_file(dir.path(), "d.txt", "d1", "docs: explain parsers");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .arg("generate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("### Added\n- add export"))
+        .stdout(predicate::str::contains(
+            "### Fixed\n- Fix login redirect (WEB-12)",
+        ))
+        .stdout(predicate::str::contains(
+            "### Documentation\n- explain parsers",
+        ))
+        .stdout(predicate::str::contains("Merge branch").not());
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args([
+            "test-parser",
+            "[WEB-7] Fix crash",
+            "Merge branch 'x'",
+            "Update README",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[WEB-7] Fix crash\n  matched: rule 2",
+        ))
+        .stdout(predicate::str::contains("  Fixed: Fix crash (WEB-7)\n"))
+        .stdout(predicate::str::contains(
+            "Merge branch 'x'\n  matched: rule 3 `^Merge branch`\n  skipped: rule sets skip\n",
+        ))
+        .stdout(predicate::str::contains(
+            "Update README\n  matched: nothing\n  Other: Update README",
+        ));
+}
+
+#[test]
+fn preprocessors_and_postprocessors_rewrite_notes() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    fs::write(
+        dir.path().join("changelog.toml"),
+        r#"[[commit_preprocessors]]
+pattern = '^Merge pull request #(\d+) from \S+\s+(.+)'
+replace = '$2 (#$1)'
+
+[[note_postprocessors]]
+pattern = '\b([A-Z]+-\d+)\b'
+replace = '[$1](https://jira.example.com/browse/$1)'
+
+[[note_postprocessors]]
+pattern = '\B@([\w-]+)'
+replace = '[@$1](https://github.com/$1)'
+
+[[note_postprocessors]]
+pattern = '\(#(\d+)\)'
+replace = '([#$1](https://github.com/acme/widgets/issues/$1))'
+"#,
+    )
+    .expect("write config");
+
+    commit_file(
+        dir.path(),
+        "a.txt",
+        "a1",
+        "Merge pull request #7 from acme/topic\n\nfeat: add export",
+    );
+    commit_file(
+        dir.path(),
+        "b.txt",
+        "b1",
+        "fix: handle WEB-12, thanks @jane",
+    );
+
+    let linked_fix = "- handle [WEB-12](https://jira.example.com/browse/WEB-12), thanks [@jane](https://github.com/jane)";
+    bin_cmd()
+        .current_dir(dir.path())
+        .arg("generate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "### Added\n- add export ([#7](https://github.com/acme/widgets/issues/7))",
+        ))
+        .stdout(predicate::str::contains(linked_fix))
+        .stdout(predicate::str::contains("Merge pull request").not());
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["release", "--version", "1.0.0"])
+        .assert()
+        .success();
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("changelog");
+    assert!(changelog.contains(linked_fix));
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["test-parser", "Merge pull request #9 from acme/fix\n\nfix: tidy"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Merge pull request #9 from acme/fix\n  preprocessed: fix: tidy (#9)\n  matched: conventional commit\n",
+        ));
+}
+
+#[test]
+fn generate_renders_template_with_release_context() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    run_git(
+        dir.path(),
+        &["remote", "add", "origin", "git@github.com:acme/widgets.git"],
+    );
+
+    commit_file(dir.path(), "a.txt", "a1", "feat: initial feature");
+    run_git(dir.path(), &["tag", "v0.1.0"]);
+    commit_file(
+        dir.path(),
+        "b.txt",
+        "b1",
+        "feat(api): add <T> endpoint (#7)",
+    );
+    commit_file(dir.path(), "c.txt", "c1", "fix: resolve bug");
+    run_git(dir.path(), &["tag", "v0.2.0"]);
+
+    fs::write(
+        dir.path().join("notes.hbs"),
+        "## {{version}} (from {{previous_version}}) {{compare_url}}\n\
+{{#each sections}}\n\
+### {{name}}\n\
+{{#each notes}}\n\
+- {{#if scope}}{{scope}}: {{/if}}{{truncate text 18}} {{commit_link hash}} by {{author}}\n\
+{{/each}}\n\
+{{/each}}\n\
+{{pluralize contributors \"contributor\"}}, {{pluralize commits \"commit\"}}\n",
+    )
+    .expect("write template");
+
+    let output = bin_cmd()
+        .current_dir(dir.path())
+        .args([
+            "generate",
+            "--since",
+            "v0.1.0",
+            "--until",
+            "v0.2.0",
+            "--template",
+            "notes.hbs",
+        ])
+        .output()
+        .expect("run generate");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("utf8");
+
+    assert!(stdout.contains(
+        "## 0.2.0 (from 0.1.0) https://github.com/acme/widgets/compare/v0.1.0...v0.2.0\n"
+    ));
+    assert!(stdout.contains("### Added\n- api: add <T> endpoint… ["));
+    assert!(stdout.contains("](https://github.com/acme/widgets/commit/"));
+    assert!(stdout.contains(") by Test User\n### Fixed\n- resolve bug ["));
+    assert!(stdout.contains("1 contributor, 2 commits"));
+}
+
+#[test]
+fn generate_uses_named_templates_and_user_overrides() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    commit_file(dir.path(), "a.txt", "a1", "feat(api): add endpoint");
+    commit_file(dir.path(), "b.txt", "b1", "fix: resolve bug");
+
+    let user_templates = tempdir().expect("template dir");
+    bin_cmd()
+        .current_dir(dir.path())
+        .env("CHANGELOGGEN_TEMPLATE_DIR", user_templates.path())
+        .args(["generate", "--template", "compact"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- **Added:** api: add endpoint\n- **Fixed:** resolve bug\n",
+        ));
+
+    // Overriding the `note` partial changes every built-in that uses it.
+    fs::write(
+        user_templates.path().join("note.hbs"),
+        "{{text}} [{{short_hash}}]",
+    )
+    .expect("write partial");
+    fs::write(
+        user_templates.path().join("team.hbs"),
+        "{{#each sections}}{{name}}: {{#each notes}}{{> note}} {{/each}}\n{{/each}}",
+    )
+    .expect("write template");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .env("CHANGELOGGEN_TEMPLATE_DIR", user_templates.path())
+        .args(["generate", "--template", "keepachangelog"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(r"### Added\n- add endpoint \[[0-9a-f]{7}\]\n")
+                .expect("regex"),
+        );
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .env("CHANGELOGGEN_TEMPLATE_DIR", user_templates.path())
+        .args(["generate", "--template", "team"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed: resolve bug ["));
+
+    bin_cmd()
+        .env("CHANGELOGGEN_TEMPLATE_DIR", user_templates.path())
+        .args(["templates", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("github-release  built-in:"))
+        .stdout(predicate::str::contains("note.hbs"))
+        .stdout(predicate::str::contains("team "));
+
+    bin_cmd()
+        .env("CHANGELOGGEN_TEMPLATE_DIR", user_templates.path())
+        .args(["templates", "show", "slack"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("*{{version}}*"));
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["generate", "--template", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown template 'missing'"));
+}
+
+#[test]
+fn release_and_show_apply_document_templates() {
+    let dir = tempdir().expect("tempdir");
+    run_git(dir.path(), &["init"]);
+    run_git(dir.path(), &["config", "user.name", "Test User"]);
+    run_git(dir.path(), &["config", "user.email", "test@example.com"]);
+    run_git(
+        dir.path(),
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://github.com/acme/widgets.git",
+        ],
+    );
+
+    let templates = tempdir().expect("template dir");
+    fs::write(
+        templates.path().join("release.hbs"),
+        "## [{{version}}]({{compare_url}}){{#if date}} - {{date}}{{/if}}\n\n\
+{{#each sections}}\n\
+### {{name}}\n\
+{{#each notes}}\n\
+- {{this}}\n\
+{{/each}}\n\n\
+{{/each}}\n",
+    )
+    .expect("write release partial");
+    fs::write(
+        dir.path().join("changelog.toml"),
+        "[changelog]\ntemplate = \"document\"\n",
+    )
+    .expect("write config");
+
+    commit_file(dir.path(), "a.txt", "a1", "feat: initial feature");
+    bin_cmd()
+        .current_dir(dir.path())
+        .env("CHANGELOGGEN_TEMPLATE_DIR", templates.path())
+        .args(["release", "--version", "0.1.0"])
+        .assert()
+        .success();
+    run_git(dir.path(), &["tag", "v0.1.0"]);
+    commit_file(dir.path(), "b.txt", "b1", "fix: resolve bug");
+    bin_cmd()
+        .current_dir(dir.path())
+        .env("CHANGELOGGEN_TEMPLATE_DIR", templates.path())
+        .args(["release", "--version", "0.2.0"])
+        .assert()
+        .success();
+
+    let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    assert!(changelog
+        .contains("## [0.2.0](https://github.com/acme/widgets/compare/v0.1.0...v0.2.0) - "));
+    assert!(
+        changelog.contains("## [0.1.0](https://github.com/acme/widgets/releases/tag/v0.1.0) - ")
+    );
+    assert!(changelog.contains("### Fixed\n- resolve bug"));
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["validate", "--strict"])
+        .assert()
+        .success();
+
+    // The built-in document template reads the linked headings back,
+    // dates included.
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--version", "0.1.0", "--template", "document"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"## \[0\.1\.0\] - \d{4}-\d{2}-\d{2}\n").expect("regex"));
+
+    fs::write(dir.path().join("broken.hbs"), "# {{title}}\n").expect("write template");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--template", "broken.hbs"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "template 'broken' must render one release heading per release",
+        ));
+
+    // Headings alone are not enough: notes must survive too.
+    fs::write(
+        dir.path().join("headings.hbs"),
+        "# {{title}}\n\n{{#each releases}}{{heading}}\n\n{{/each}}",
+    )
+    .expect("write template");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args([
+            "release",
+            "--version",
+            "0.3.0",
+            "--template",
+            "headings.hbs",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "template 'headings' does not preserve release 0.3.0: the notes under Fixed changed",
+        ));
+    let unchanged = fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("read changelog");
+    assert!(unchanged.contains("### Fixed\n- resolve bug"));
+    assert!(!unchanged.contains("0.3.0"));
+}
+
+#[test]
+fn workspace_release_writes_one_changelog_per_crate() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(root, &["config", "user.name", "Test User"]);
+    run_git(root, &["config", "user.email", "test@example.com"]);
+
+    for (path, version) in [
+        ("crates/core", "version = \"0.0.1\""),
+        ("crates/cli", "version.workspace = true"),
+    ] {
+        fs::create_dir_all(root.join(path)).expect("mkdir");
+        let name = path.replace("crates/", "app-");
+        fs::write(
+            root.join(path).join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\n{}\n", name, version),
+        )
+        .expect("write manifest");
+    }
+    commit_file(
+        root,
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+        "chore: set up workspace",
+    );
+    commit_file(root, "crates/core/lib.rs", "1", "feat(core): add engine");
+    commit_file(root, "crates/cli/main.rs", "1", "feat(cli): add command");
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["release", "--workspace", "--version", "0.1.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "crate app-cli inherits its version from the workspace",
+        ));
+    assert!(!root.join("crates/core/CHANGELOG.md").exists());
+
+    commit_file(
+        root,
+        "crates/cli/Cargo.toml",
+        "[package]\nname = \"app-cli\"\nversion = \"0.0.1\"\n",
+        "chore: version the cli on its own",
+    );
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["release", "--workspace", "--version", "0.1.0"])
+        .assert()
+        .success();
+    run_git(root, &["add", "."]);
+    run_git(root, &["commit", "-m", "chore: release 0.1.0"]);
+    run_git(root, &["tag", "app-core-v0.1.0"]);
+    run_git(root, &["tag", "app-cli-v0.1.0"]);
+
+    let core = fs::read_to_string(root.join("crates/core/CHANGELOG.md")).expect("core changelog");
+    assert!(core.contains("add engine"));
+    assert!(!core.contains("add command"));
+    let manifest = fs::read_to_string(root.join("crates/core/Cargo.toml")).expect("manifest");
+    assert!(manifest.contains("version = \"0.1.0\""));
+
+    bin_cmd()
+        .current_dir(root)
+        .args([
+            "release",
+            "--workspace",
+            "--version",
+            "0.2.0",
+            "--version-file",
+            "VERSION",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    commit_file(
+        root,
+        "crates/cli/main.rs",
+        "2",
+        "fix(cli): handle empty input",
+    );
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["release", "--workspace", "--bump", "patch"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No changes for app-core"));
+
+    let cli = fs::read_to_string(root.join("crates/cli/CHANGELOG.md")).expect("cli changelog");
+    assert!(cli.contains("## [0.1.1] -"));
+    assert!(cli.contains("handle empty input"));
+    let core = fs::read_to_string(root.join("crates/core/CHANGELOG.md")).expect("core changelog");
+    assert!(!core.contains("0.1.1"));
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["generate", "--workspace", "--since", "app-cli-v0.1.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## app-cli"))
+        .stdout(predicate::str::contains("## app-core").not());
+}
+
+#[test]
+fn generate_filters_commits_by_path() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(root, &["config", "user.name", "Test User"]);
+    run_git(root, &["config", "user.email", "test@example.com"]);
+
+    fs::create_dir_all(root.join("src")).expect("mkdir src");
+    fs::create_dir_all(root.join("docs")).expect("mkdir docs");
+    commit_file(root, "src/lib.rs", "1", "feat: add parser");
+    commit_file(root, "docs/guide.md", "1", "docs: write guide");
+    fs::write(root.join("src/README.md"), "notes").expect("write readme");
+    commit_file(root, "docs/guide.md", "2", "fix: correct examples");
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["generate", "--path", "src/", "--exclude-path", "*.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("add parser"))
+        .stdout(predicate::str::contains("write guide").not())
+        .stdout(predicate::str::contains("correct examples").not());
+
+    bin_cmd()
+        .current_dir(root.join("docs"))
+        .args(["generate", "--file", "../CHANGELOG.md", "--path", "."])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("write guide"))
+        .stdout(predicate::str::contains("add parser").not());
+}
+
+#[test]
+fn config_file_supplies_defaults_and_flags_win() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(root, &["config", "user.name", "Test User"]);
+    run_git(root, &["config", "user.email", "test@example.com"]);
+
+    fs::write(
+        root.join("changelog.toml"),
+        "[commit_types]\nfeat = \"Features\"\n\n[changelog]\nfile = \"docs/CHANGES.md\"\nheader = \"plain\"\ntag_prefix = \"release-\"\nignore_markers = [\"[no log]\"]\n",
+    )
+    .expect("write config");
+    commit_file(root, "a.txt", "1", "feat: first feature");
+    run_git(root, &["tag", "release-0.1.0"]);
+    commit_file(root, "a.txt", "2", "feat: second feature");
+    commit_file(root, "a.txt", "3", "fix: tweak build [no log]");
+
+    fs::create_dir_all(root.join("sub")).expect("mkdir sub");
+    bin_cmd()
+        .current_dir(root.join("sub"))
+        .args(["generate", "--since", "release-0.1.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("### Features"))
+        .stdout(predicate::str::contains("second feature"))
+        .stdout(predicate::str::contains("tweak build").not());
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["release", "--version", "0.2.0"])
+        .assert()
+        .success();
+    let changes = fs::read_to_string(root.join("docs/CHANGES.md")).expect("configured file");
+    assert!(changes.contains("## 0.2.0 - "));
+    assert!(changes.contains("second feature"));
+    assert!(!changes.contains("first feature"));
+
+    // The configured file is found next to the config from a subdirectory.
+    bin_cmd()
+        .current_dir(root.join("sub"))
+        .args(["validate", "--strict"])
+        .assert()
+        .success();
+    assert!(!root.join("sub/docs").exists());
+
+    bin_cmd()
+        .current_dir(root)
+        .env("CHANGELOGGEN_HEADER", "version-only")
+        .args(["release", "--version", "0.3.0", "--file", "OTHER.md"])
+        .assert()
+        .success();
+    let other = fs::read_to_string(root.join("OTHER.md")).expect("flag file");
+    assert!(other.contains("## [0.3.0]\n"));
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["validate", "--config", "missing.toml"])
+        .assert()
+        .failure()
+        .code(3);
+}
+
+#[test]
+fn generate_lists_github_milestone_items() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(
+        root,
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://github.com/acme/widgets.git",
+        ],
+    );
+
+    let mut server = mockito::Server::new();
+    let milestones = server
+        .mock("GET", "/repos/acme/widgets/milestones/3")
+        .match_header("authorization", "Bearer test-token")
+        .with_body(r#"{"number": 3, "title": "v2.0"}"#)
+        .create();
+    let issues = server
+        .mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/repos/acme/widgets/issues\?milestone=3".to_string()),
+        )
+        .with_body(
+            r#"[
+                {"number": 10, "title": "Crash when config is empty", "html_url": "https://github.com/acme/widgets/issues/10",
+                 "labels": [{"name": "bug"}]},
+                {"number": 11, "title": "feat(cli): add --json", "html_url": "https://github.com/acme/widgets/pull/11",
+                 "labels": [], "pull_request": {"merged_at": "2024-03-01T00:00:00Z"}},
+                {"number": 12, "title": "Rejected idea", "html_url": "https://github.com/acme/widgets/pull/12",
+                 "labels": [{"name": "enhancement"}], "pull_request": {"merged_at": null}}
+            ]"#,
+        )
+        .create();
+
+    bin_cmd()
+        .current_dir(root)
+        .env("GITHUB_TOKEN", "test-token")
+        .args([
+            "generate",
+            "--github",
+            "--milestone",
+            "3",
+            "--github-api-url",
+            &server.url(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("### Fixed"))
+        .stdout(predicate::str::contains(
+            "- Crash when config is empty ([#10](https://github.com/acme/widgets/issues/10))",
+        ))
+        .stdout(predicate::str::contains("- **cli:** add --json ([#11]"))
+        .stdout(predicate::str::contains("Rejected idea").not());
+
+    milestones.assert();
+    issues.assert();
+}
+
+#[test]
+fn generate_github_links_pull_requests_and_caches_them() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path().join("repo");
+    let cache = dir.path().join("cache");
+    fs::create_dir_all(&root).expect("mkdir repo");
+    let root = root.as_path();
+    run_git(root, &["init"]);
+    run_git(root, &["config", "user.name", "Test User"]);
+    run_git(root, &["config", "user.email", "test@example.com"]);
+    run_git(
+        root,
+        &["remote", "add", "origin", "git@github.com:acme/widgets.git"],
+    );
+    commit_file(root, "a.txt", "1", "feat: add export (#12)");
+    commit_file(
+        root,
+        "a.txt",
+        "2",
+        "Merge pull request #13 from bob/empty-input",
+    );
+    commit_file(root, "a.txt", "3", "fix: plain commit");
+
+    let mut server = mockito::Server::new();
+    let pr12 = server
+        .mock("GET", "/repos/acme/widgets/pulls/12")
+        .with_body(
+            r#"{"number": 12, "title": "feat: add export", "html_url": "https://github.com/acme/widgets/pull/12",
+                "user": {"login": "alice"}, "labels": []}"#,
+        )
+        .expect(1)
+        .create();
+    let pr13 = server
+        .mock("GET", "/repos/acme/widgets/pulls/13")
+        .with_body(
+            r#"{"number": 13, "title": "Crash on empty input", "html_url": "https://github.com/acme/widgets/pull/13",
+                "user": {"login": "bob"}, "labels": [{"name": "bug"}]}"#,
+        )
+        .expect(1)
+        .create();
+
+    for _ in 0..2 {
+        bin_cmd()
+            .current_dir(root)
+            .env("CHANGELOGGEN_CACHE_DIR", &cache)
+            .args(["generate", "--github", "--github-api-url", &server.url()])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "- add export ([#12](https://github.com/acme/widgets/pull/12)) by @alice",
+            ))
+            .stdout(predicate::str::contains(
+                "### Fixed\n- Crash on empty input ([#13](https://github.com/acme/widgets/pull/13)) by @bob",
+            ))
+            .stdout(predicate::str::contains("- plain commit\n"));
+    }
+
+    pr12.assert();
+    pr13.assert();
+    assert!(cache.join("acme/widgets/12.json").is_file());
+
+    // An unusable cache directory only costs a warning.
+    let blocked = dir.path().join("not-a-dir");
+    fs::write(&blocked, "").expect("write file");
+    bin_cmd()
+        .current_dir(root)
+        .env("CHANGELOGGEN_CACHE_DIR", &blocked)
+        .args(["generate", "--github", "--github-api-url", &server.url()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- add export ([#12](https://github.com/acme/widgets/pull/12)) by @alice",
+        ))
+        .stderr(predicate::str::contains(
+            "Warning: cannot cache pull request #12:",
+        ));
+}
+
+#[test]
+fn publish_creates_then_updates_github_release() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(
+        root,
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://github.com/acme/widgets.git",
+        ],
+    );
+    fs::write(
+        root.join("CHANGELOG.md"),
+        "# Changelog\n\n## [1.1.0-rc.1] - 2024-02-01\n\n### Added\n- preview\n\n## [1.0.0] - 2024-01-01\n\n### Fixed\n- crash on start\n",
+    )
+    .expect("write changelog");
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["publish", "--version", "1.0.0", "--draft", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Would publish GitHub release v1.0.0 for acme/widgets (draft)",
+        ))
+        .stdout(predicate::str::contains("### Fixed\n- crash on start"));
+
+    for flag in ["--draft", "--prerelease"] {
+        bin_cmd()
+            .current_dir(root)
+            .args(["publish", "--forge", "gitlab", flag, "--dry-run"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "GitLab releases have no draft or pre-release state",
+            ));
+    }
+
+    let mut server = mockito::Server::new();
+    let missing = server
+        .mock("GET", "/repos/acme/widgets/releases?per_page=100")
+        .with_body("[]")
+        .create();
+    let create = server
+        .mock("POST", "/repos/acme/widgets/releases")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "tag_name": "v1.1.0-rc.1",
+            "body": "### Added\n- preview",
+            "draft": false,
+            "prerelease": true,
+        })))
+        .with_status(201)
+        .with_body(
+            r#"{"id": 5, "html_url": "https://github.com/acme/widgets/releases/tag/v1.1.0-rc.1"}"#,
+        )
+        .create();
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["publish", "--github-api-url", &server.url()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created GitHub release v1.1.0-rc.1",
+        ));
+    missing.assert();
+    create.assert();
+    missing.remove();
+
+    let existing = server
+        .mock("GET", "/repos/acme/widgets/releases?per_page=100")
+        .with_body(
+            r#"[{"id": 4, "html_url": "https://github.com/acme/widgets/releases/tag/v1.0.0", "tag_name": "v1.0.0"}]"#,
+        )
+        .create();
+    let update = server
+        .mock("PATCH", "/repos/acme/widgets/releases/4")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "body": "### Fixed\n- crash on start",
+        })))
+        .with_body(
+            r#"{"id": 4, "html_url": "https://github.com/acme/widgets/releases/tag/v1.0.0"}"#,
+        )
+        .create();
+
+    bin_cmd()
+        .current_dir(root)
+        .args([
+            "publish",
+            "--version",
+            "1.0.0",
+            "--github-api-url",
+            &server.url(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated GitHub release v1.0.0"));
+    existing.assert();
+    update.assert();
+}
+
+#[test]
+fn release_commits_and_tags_with_notes() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(root, &["config", "user.name", "Test User"]);
+    run_git(root, &["config", "user.email", "test@example.com"]);
+    commit_file(root, "VERSION", "0.9.0\n", "chore: track version");
+    commit_file(root, "a.txt", "1", "feat: add importer");
+    fs::write(root.join("VERSION"), "1.0.0\n").expect("write version");
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["release", "--version", "1.0.0", "--commit", "--tag"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--allow-dirty"));
+
+    bin_cmd()
+        .current_dir(root)
+        .args([
+            "release",
+            "--version",
+            "1.0.0",
+            "--commit",
+            "--tag",
+            "--stage",
+            "VERSION",
+            "--allow-dirty",
+            "--commit-message",
+            "release {tag}",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Tagged v1.0.0"));
+
+    let git_output = |args: &[&str]| {
+        let output = ProcessCommand::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .expect("run git");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    assert_eq!(
+        git_output(&["log", "-1", "--format=%s"]).trim(),
+        "release v1.0.0"
+    );
+    assert!(git_output(&["status", "--porcelain"]).trim().is_empty());
+    assert_eq!(git_output(&["cat-file", "-t", "v1.0.0"]).trim(), "tag");
+    let tag_message = git_output(&["tag", "-l", "--format=%(contents)", "v1.0.0"]);
+    assert!(tag_message.contains("### Added\n- add importer"));
+}
+
+#[test]
+fn release_bumps_configured_version_files() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(root, &["config", "user.name", "Test User"]);
+    run_git(root, &["config", "user.email", "test@example.com"]);
+
+    fs::write(
+        root.join("changelog.toml"),
+        "[[version_files]]\npath = \"Cargo.toml\"\n\n[[version_files]]\npath = \"package.json\"\n\n[[version_files]]\npath = \"VERSION\"\n",
+    )
+    .expect("write config");
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"app\"\nversion = \"1.2.3\" # bumped by release\n",
+    )
+    .expect("write manifest");
+    fs::write(
+        root.join("package.json"),
+        "{\n  \"name\": \"app\",\n  \"version\": \"1.2.3\"\n}\n",
+    )
+    .expect("write package.json");
+    commit_file(root, "VERSION", "1.2.3\n", "chore: initial layout");
+    commit_file(root, "a.txt", "1", "feat: add importer");
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["release", "--bump", "minor", "--commit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Set version 1.3.0 in"));
+
+    assert_eq!(
+        fs::read_to_string(root.join("Cargo.toml")).expect("manifest"),
+        "[package]\nname = \"app\"\nversion = \"1.3.0\" # bumped by release\n"
+    );
+    assert!(fs::read_to_string(root.join("package.json"))
+        .expect("package.json")
+        .contains("\"version\": \"1.3.0\"\n"));
+    assert_eq!(
+        fs::read_to_string(root.join("VERSION")).expect("version"),
+        "1.3.0\n"
+    );
+    let changelog = fs::read_to_string(root.join("CHANGELOG.md")).expect("changelog");
+    assert!(changelog.contains("## [1.3.0] -"));
+
+    let status = ProcessCommand::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(root)
+        .output()
+        .expect("git status");
+    assert!(String::from_utf8_lossy(&status.stdout).trim().is_empty());
+}
+
+#[test]
+fn show_prints_json_and_yaml() {
+    let dir = tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("CHANGELOG.md"),
+        "# Changelog\n\nAll notable changes.\n\n## [1.1.0] - 2026-01-01\n\n### Added\n- export\n\n## [1.0.0] - 2025-12-01\n\n### Fixed\n- crash\n",
+    )
+    .expect("write changelog");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--version", "1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "# Changelog\n\nAll notable changes.\n\n## [1.0.0]",
+        ));
+
+    let output = bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--format", "json", "--version", "1.1.0"])
+        .output()
+        .expect("run show");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json output");
+    assert_eq!(json["title"], "Changelog");
+    assert_eq!(json["preamble"], "All notable changes.");
+    assert_eq!(json["releases"].as_array().expect("releases").len(), 1);
+    assert_eq!(json["releases"][0]["version"], "1.1.0");
+    assert_eq!(json["releases"][0]["sections"]["Added"][0], "export");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--format", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- version: 1.0.0\n  date: 2025-12-01\n",
+        ));
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--format", "json", "--converge"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--converge"));
+}
+
+#[test]
+fn import_renders_structured_changelog_to_markdown() {
+    let dir = tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("changelog.yaml"),
+        "title: Changelog\nreleases:\n- version: 1.1.0\n  date: 2026-01-01\n  sections:\n    Added:\n    - export\n- version: 1.0.0\n  date: 2025-12-01\n  sections:\n    Fixed:\n    - crash\n",
+    )
+    .expect("write yaml");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["validate", "--file", "changelog.yaml", "--strict"])
+        .assert()
+        .success();
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["import", "changelog.yaml", "--header", "plain"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Wrote CHANGELOG.md from changelog.yaml",
+        ));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("CHANGELOG.md")).expect("changelog"),
+        "# Changelog\n\n## 1.1.0 - 2026-01-01\n\n### Added\n- export\n\n## 1.0.0 - 2025-12-01\n\n### Fixed\n- crash\n"
+    );
+
+    fs::write(
+        dir.path().join("unsorted.json"),
+        r#"{"title": "Changelog", "releases": [{"version": "1.0.0", "date": null, "sections": {}}, {"version": "2.0.0", "date": null, "sections": {}}]}"#,
+    )
+    .expect("write json");
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["import", "unsorted.json", "--file", "OTHER.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("validation failed"));
+    assert!(!dir.path().join("OTHER.md").exists());
+}
+
+#[test]
+fn release_updates_asciidoc_and_rst_changelogs() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    run_git(root, &["init"]);
+    run_git(root, &["config", "user.name", "Test User"]);
+    run_git(root, &["config", "user.email", "test@example.com"]);
+    commit_file(root, "a.txt", "1", "feat: add importer");
+
+    for file in ["CHANGELOG.adoc", "CHANGELOG.rst"] {
+        bin_cmd()
+            .current_dir(root)
+            .args(["new", "--file", file])
+            .assert()
+            .success();
+        bin_cmd()
+            .current_dir(root)
+            .args(["release", "--version", "0.1.0", "--file", file])
+            .assert()
+            .success();
+    }
+    run_git(root, &["tag", "v0.1.0"]);
+    commit_file(root, "b.txt", "2", "fix: handle `--force` (#7)");
+
+    for file in ["CHANGELOG.adoc", "CHANGELOG.rst"] {
+        bin_cmd()
+            .current_dir(root)
+            .args(["release", "--bump", "auto", "--file", file])
+            .assert()
+            .success();
+        bin_cmd()
+            .current_dir(root)
+            .args(["validate", "--strict", "--file", file])
+            .assert()
+            .success();
+    }
+
+    let adoc = fs::read_to_string(root.join("CHANGELOG.adoc")).expect("adoc");
+    assert!(adoc.starts_with("= Changelog\n\n== [0.1.1] - "));
+    assert!(adoc.contains("=== Fixed\n* handle `--force` (#7)\n"));
+    assert!(adoc.contains("=== Added\n* add importer\n"));
+
+    let rst = fs::read_to_string(root.join("CHANGELOG.rst")).expect("rst");
+    assert!(rst.starts_with("Changelog\n=========\n\n[0.1.1] - "));
+    assert!(rst.contains("Fixed\n~~~~~\n\n- handle ``--force`` (#7)\n"));
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["show", "--file", "CHANGELOG.rst", "--version", "0.1.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added\n~~~~~\n\n- add importer"));
+    bin_cmd()
+        .current_dir(root)
+        .args(["show", "--file", "CHANGELOG.adoc", "--format", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## [0.1.0]"));
+}
+
+#[test]
+fn show_renders_html_page_and_feeds() {
+    let dir = tempdir().expect("tempdir");
+    fs::write(
+        dir.path().join("CHANGELOG.md"),
+        "# Changelog\n\n## [1.1.0] - 2026-01-02\n\n### Added\n- export `Vec<T>`\n\n## [1.0.0] - 2025-12-01\n\n### Fixed\n- crash\n",
+    )
+    .expect("write changelog");
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--format", "html"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<!DOCTYPE html>"))
+        .stdout(predicate::str::contains(
+            "<section class=\"release\" id=\"1.1.0\">\n<h2><a href=\"#1.1.0\">1.1.0</a>",
+        ))
+        .stdout(predicate::str::contains("<code>Vec&lt;T&gt;</code>"));
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args([
+            "show",
+            "--format",
+            "atom",
+            "--site-url",
+            "https://acme.dev/changelog",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<feed xmlns=\"http://www.w3.org/2005/Atom\">",
+        ))
+        .stdout(predicate::str::contains(
+            "<link href=\"https://acme.dev/changelog#1.0.0\"/>",
+        ));
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args(["show", "--format", "rss", "--version", "1.1.0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--format rss requires --site-url"));
+
+    bin_cmd()
+        .current_dir(dir.path())
+        .args([
+            "show",
+            "--format",
+            "rss",
+            "--version",
+            "1.1.0",
+            "--site-url",
+            "https://acme.dev/changelog",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<link>https://acme.dev/changelog</link>",
+        ))
+        .stdout(predicate::str::contains(
+            "<pubDate>Fri, 02 Jan 2026 00:00:00 +0000</pubDate>",
+        ))
+        .stdout(predicate::str::contains("<item>").count(1));
+}
+
+#[test]
+fn export_packaging_changelogs_and_import_debian() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    fs::write(
+        root.join("changelog.toml"),
+        "[packaging]\npackage = \"widgets\"\nmaintainer = \"Jane Doe <jane@example.com>\"\ndistribution = \"noble\"\n",
+    )
+    .expect("write config");
+    fs::write(
+        root.join("CHANGELOG.md"),
+        "# Changelog\n\n## [2.0.0-rc.1] - 2026-01-02\n\n### Added\n- export ([#12](https://example.com/pull/12))\n\n## [1.0.0] - 2025-12-01\n\n### Fixed\n- crash\n",
+    )
+    .expect("write changelog");
+
+    bin_cmd()
+        .current_dir(root)
+        .args([
+            "export",
+            "--format",
+            "debian",
+            "--output",
+            "debian/changelog",
+        ])
+        .assert()
+        .success();
+    let debian = fs::read_to_string(root.join("debian/changelog")).expect("debian");
+    assert!(debian.starts_with(
+        "widgets (2.0.0~rc1-1) noble; urgency=medium\n\n  * Added:\n    - export (#12)\n\n -- Jane Doe <jane@example.com>  Fri, 02 Jan 2026 00:00:00 +0000\n"
+    ));
+
+    bin_cmd()
+        .current_dir(root)
+        .args([
+            "export",
+            "--format",
+            "rpm",
+            "--maintainer",
+            "Bob <bob@example.com>",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "* Mon Dec 01 2025 Bob <bob@example.com> - 1.0.0-1\n- Fixed: crash\n",
+        ));
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["export", "--format", "appstream"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "<release version=\"2.0.0~rc1\" date=\"2026-01-02\" type=\"development\">",
+        ));
+
+    bin_cmd()
+        .current_dir(root)
+        .args(["import", "debian/changelog", "--file", "IMPORTED.md"])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(root.join("IMPORTED.md")).expect("imported"),
+        "# Changelog\n\n## [2.0.0-rc.1] - 2026-01-02\n\n### Added\n- export (#12)\n\n## [1.0.0] - 2025-12-01\n\n### Fixed\n- crash\n"
+    );
+}
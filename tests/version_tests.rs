@@ -8,6 +8,7 @@ mod tests {
         let commit = Commit {
             message: "Initial commit".to_string(),
             hash: "abc123".to_string(),
+            ..Default::default()
         };
 
         assert_eq!(commit.message, "Initial commit");
@@ -20,6 +21,7 @@ mod tests {
         let commit = Commit {
             message: "Test commit".to_string(),
             hash: "def456".to_string(),
+            ..Default::default()
         };
 
         let cloned = commit.clone();